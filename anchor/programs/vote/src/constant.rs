@@ -5,3 +5,23 @@ pub const MAX_NAME_LENGTH: usize = 50;
 pub const MAX_PLUS_VOTES: u8 = 10;
 pub const MAX_MINUS_VOTES: u8 = 5;
 pub const UUID_LENGTH: usize = 36;
+pub const MIN_ID_NUMBER_LENGTH: usize = 7;
+pub const MAX_ID_NUMBER_LENGTH: usize = 12;
+pub const MAX_REGISTRARS: usize = 10;
+pub const MAX_REASON_LENGTH: usize = 100;
+pub const REVEAL_PERIOD: i64 = 24 * 60 * 60;
+pub const MAX_PROPOSAL_LIFETIME: i64 = 7 * 24 * 60 * 60;
+pub const VOTE_SCALE: u64 = 1_000_000_000;
+pub const MAX_ELIGIBILITY_RULES: usize = 8;
+pub const MAX_CATEGORIES: usize = 32;
+pub const MAX_CATEGORY_NAME_LENGTH: usize = 32;
+pub const MAX_ALLOWED_VOTER_TYPES: usize = 8;
+pub const MAX_ORG_ID_LENGTH: usize = 32;
+pub const MAX_ORG_ADMINS: usize = 5;
+pub const MAX_ELECTION_CREATORS: usize = 10;
+pub const MAX_COMMITTEE_MEMBERS: usize = 10;
+pub const MAX_OFFICERS: usize = 5;
+pub const MAX_OFFICER_PERMISSIONS: usize = 6;
+pub const MAX_ROLL_BATCH: usize = 10;
+pub const MAX_STATUS_HISTORY: usize = 8;
+pub const MAX_DELEGATORS: u32 = 10;
@@ -0,0 +1,52 @@
+// ed25519.rs
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+
+// Layout of the native program's instruction data: a signature count and
+// padding byte, then one block of u16 offsets per signature
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+
+// Offsets may point into other instructions; only data carried by the
+// ed25519 instruction itself is accepted
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+// True when `ix` is an ed25519 program instruction verifying exactly one
+// signature by `signer` over `message`. The runtime has already rejected the
+// transaction if the signature itself is invalid.
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> bool {
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return false;
+    }
+
+    let data = &ix.data;
+    if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+        return false;
+    }
+
+    let read = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_offset = read(2) as usize;
+    let signature_ix = read(4);
+    let pubkey_offset = read(6) as usize;
+    let pubkey_ix = read(8);
+    let message_offset = read(10) as usize;
+    let message_size = read(12) as usize;
+    let message_ix = read(14);
+
+    if
+        signature_ix != THIS_INSTRUCTION ||
+        pubkey_ix != THIS_INSTRUCTION ||
+        message_ix != THIS_INSTRUCTION ||
+        signature_offset + SIGNATURE_LEN > data.len()
+    {
+        return false;
+    }
+
+    let pubkey = data.get(pubkey_offset..pubkey_offset + PUBKEY_LEN);
+    let signed = data.get(message_offset..message_offset + message_size);
+    pubkey == Some(signer.as_ref()) && signed == Some(message)
+}
@@ -55,8 +55,143 @@ pub enum D21Error {
     #[msg("User type not allowed for this election")]
     UserTypeNotAllowed,
 
-    #[msg("Election must allow at least one voter type")]
+    #[msg("Election must allow between 1 and 8 voter types")]
     InvalidVoterTypes,
+
+    #[msg("End time must be after the start time and in the future")]
+    InvalidElectionTimes,
+
+    #[msg("Election has not started yet")]
+    ElectionNotStarted,
+
+    #[msg("Election voting period has ended")]
+    ElectionPeriodEnded,
+
+    #[msg("Election has no scheduled end time")]
+    EndTimeNotSet,
+
+    #[msg("Election end time has not passed yet")]
+    ElectionStillRunning,
+
+    #[msg("Invalid election status transition")]
+    InvalidElectionTransition,
+
+    #[msg("Election is not in its registration phase")]
+    RegistrationClosed,
+
+    #[msg("Election is not in its voting phase")]
+    VotingNotOpen,
+
+    #[msg("Reason must be between 1 and 100 characters")]
+    InvalidReasonLength,
+
+    #[msg("Election must be ended or cancelled")]
+    ElectionNotFinal,
+
+    #[msg("This election does not allow changing a cast vote")]
+    RevoteNotAllowed,
+
+    #[msg("This election uses secret ballots; submit a commitment instead")]
+    SecretBallotRequired,
+
+    #[msg("This election does not use secret ballots")]
+    NotSecretBallot,
+
+    #[msg("Election is not in its reveal phase")]
+    RevealNotOpen,
+
+    #[msg("Revealed vote does not match the commitment")]
+    InvalidReveal,
+
+    #[msg("Ballot has already been revealed")]
+    AlreadyRevealed,
+
+    #[msg("Ballot does not fit the election's voting method")]
+    InvalidBallotForMethod,
+
+    #[msg("This election's voting method does not use the tally crank")]
+    NotTallyMethod,
+
+    #[msg("Results for this voting method come from the tally crank")]
+    TallyRequired,
+
+    #[msg("Ballots must be tallied in ascending address order")]
+    BallotOutOfOrder,
+
+    #[msg("Ballot does not belong to this election")]
+    InvalidTallyBallot,
+
+    #[msg("A credit budget is required for quadratic voting and only allowed there")]
+    InvalidCreditBudget,
+
+    #[msg("Ballot costs more credits than the voter has")]
+    InsufficientCredits,
+
+    #[msg("This voting method does not support the selected election options")]
+    UnsupportedMethodOption,
+
+    #[msg("An election can be gated by an NFT collection or an allowlist, not both")]
+    ConflictingEligibilityRules,
+
+    #[msg("Token account is not for this election's vote mint")]
+    InvalidVoteTokenAccount,
+
+    #[msg("Voter has no tokens deposited for this election")]
+    NoVotingPower,
+
+    #[msg("Sponsorship amount must be greater than zero")]
+    InvalidSponsorshipAmount,
+
+    #[msg("Signed ballot must follow an ed25519 instruction verifying the voter's signature")]
+    InvalidBallotSignature,
+
+    #[msg("Signed ballot has expired")]
+    SignedBallotExpired,
+
+    #[msg("Signed ballot nonce does not match the voter's next nonce")]
+    InvalidBallotNonce,
+
+    #[msg("Ballot has been voided")]
+    BallotVoided,
+
+    #[msg("Ballots can only be voided before tallying")]
+    BallotsClosed,
+
+    #[msg("This election is not token-weighted")]
+    NotTokenWeighted,
+
+    #[msg("Deposited tokens are locked until the election ends")]
+    TokensLocked,
+
+    #[msg("Eligibility rules must list 1-8 valid rules")]
+    InvalidEligibilityRules,
+
+    #[msg("Eligibility rules can only change before registration opens")]
+    EligibilityLocked,
+
+    #[msg("Signer is not the pending authority for this election")]
+    NotPendingAuthority,
+
+    #[msg("Officer permissions must be unique and cannot be granted to the authority")]
+    InvalidOfficerPermissions,
+
+    #[msg("Too many election officers")]
+    TooManyOfficers,
+
+    #[msg("No ballots have been counted in the current tally round")]
+    TallyRoundNotStarted,
+
+    #[msg("Rent must be refunded to the account that paid for it")]
+    InvalidRentRecipient,
+
+    #[msg("Ballot weight is too large for the election's vote counters")]
+    VoteCountOverflow,
+
+    #[msg("Close the election's sponsorship before the election itself")]
+    SponsorshipStillOpen,
+
+    #[msg("Allowlisted elections register voters by Merkle proof, not eligibility rules")]
+    AllowlistHasNoRules,
 }
 
 #[error_code]
@@ -75,6 +210,84 @@ pub enum VoterError {
 
     #[msg("Invalid voter status transition")]
     InvalidStatusTransition,
+
+    #[msg("Only ballots of suspended or revoked voters can be voided")]
+    BallotVoidNotAllowed,
+
+    #[msg("Voter has not cast a ballot yet")]
+    BallotNotCast,
+
+    #[msg("Withdraw deposited tokens before closing the voter record")]
+    TokensStillDeposited,
+
+    #[msg("This election requires a membership NFT to register")]
+    MembershipNftRequired,
+
+    #[msg("This election is not gated by an NFT collection")]
+    NotNftGated,
+
+    #[msg("NFT is not held by the voter or not verified in the election's collection")]
+    InvalidMembershipNft,
+
+    #[msg("This election allows one registration per NFT; the claim account is required")]
+    NftClaimRequired,
+
+    #[msg("This election registers voters from an allowlist; a Merkle proof is required")]
+    AllowlistProofRequired,
+
+    #[msg("This election does not use an allowlist")]
+    NotAllowlisted,
+
+    #[msg("Voter and weight are not on the election's allowlist")]
+    InvalidAllowlistProof,
+
+    #[msg("Voter was verified too recently for this election")]
+    VerificationTooRecent,
+
+    #[msg("Voter does not hold enough of the required token")]
+    InsufficientTokenBalance,
+
+    #[msg("Voter does not hold an NFT from the required collection")]
+    NftNotHeld,
+
+    #[msg("Voter's verification was not approved by the required registrar")]
+    RegistrarAttestationMissing,
+
+    #[msg("Voter has already delegated their vote")]
+    AlreadyDelegated,
+
+    #[msg("Voter has not delegated their vote")]
+    NotDelegated,
+
+    #[msg("Voters cannot delegate to themselves")]
+    SelfDelegation,
+
+    #[msg("Delegation would create a cycle")]
+    DelegationCycle,
+
+    #[msg("Delegation chain account is missing or does not belong to this election")]
+    DelegationChainBroken,
+
+    #[msg("Every voter delegating through this ballot must be supplied")]
+    DelegatorsMissing,
+
+    #[msg("Voter has delegated their vote; revoke the delegation to vote directly")]
+    VoteDelegated,
+
+    #[msg("Delegate has already cast their ballot")]
+    DelegateAlreadyVoted,
+
+    #[msg("Votes cannot be delegated in quadratic elections")]
+    DelegationNotSupported,
+
+    #[msg("Roll accounts must be the voters' ElectionVoter addresses, in order")]
+    InvalidRollEntry,
+
+    #[msg("Too many voters in one roll batch")]
+    TooManyRollEntries,
+
+    #[msg("Delegate already carries the maximum number of delegators")]
+    TooManyDelegators,
 }
 
 #[error_code]
@@ -85,4 +298,58 @@ pub enum VerificationError {
     AlreadyVerified,
     #[msg("Voter is not verified")]
     VoterNotVerified,
+    #[msg("Signer is not an authorised registrar")]
+    NotRegistrar,
+    #[msg("Category name must be between 1 and 32 characters")]
+    InvalidCategoryName,
+    #[msg("Too many voter categories")]
+    TooManyCategories,
+    #[msg("Voter category does not exist or has been retired")]
+    UnknownCategory,
+}
+
+#[error_code]
+pub enum OrganizationError {
+    #[msg("Organisation ID must be between 1 and 32 characters")]
+    InvalidOrganizationId,
+    #[msg("Signer is not an admin of this organisation")]
+    NotOrgAdmin,
+    #[msg("Signer is not allowed to create elections in this organisation")]
+    NotElectionCreator,
+    #[msg("Member already has this role")]
+    MemberAlreadyHasRole,
+    #[msg("Member does not have this role")]
+    MemberNotFound,
+    #[msg("Too many members for this role")]
+    TooManyMembers,
+    #[msg("An organisation must keep at least one admin")]
+    LastAdmin,
+    #[msg("Only the program's upgrade authority can create organisations")]
+    NotUpgradeAuthority,
+}
+
+#[error_code]
+pub enum CommitteeError {
+    #[msg("Committee must have 1-10 unique members and a threshold between 1 and its size")]
+    InvalidCommittee,
+    #[msg("The committee can only be set before registration opens")]
+    CommitteeLocked,
+    #[msg("This election is governed by a committee; submit a proposal instead")]
+    CommitteeApprovalRequired,
+    #[msg("This election is not governed by a committee")]
+    NoCommittee,
+    #[msg("Signer is not a member of the election committee")]
+    NotCommitteeMember,
+    #[msg("Member has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal does not have enough approvals yet")]
+    ThresholdNotMet,
+    #[msg("Proposal has already been executed")]
+    ProposalExecuted,
+    #[msg("Voter account does not match the proposal")]
+    ProposalVoterMismatch,
+    #[msg("Proposal expiry must be in the future and at most a week away")]
+    InvalidProposalExpiry,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
 }
@@ -0,0 +1,137 @@
+// instructions/authority.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{ MAX_OFFICERS, MAX_OFFICER_PERMISSIONS },
+    CommitteeError,
+    D21Error,
+    Election,
+    Officer,
+    OfficerPermission,
+};
+
+#[derive(Accounts)]
+pub struct ManageElectionAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+}
+
+// The new authority must sign, so a mistyped key can't take the election over
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.pending_authority == Some(new_authority.key()) @ D21Error::NotPendingAuthority,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired
+    )]
+    pub election: Account<'info, Election>,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub election: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub election: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfficerUpdated {
+    pub election: Pubkey,
+    pub officer: Pubkey,
+    pub permissions: Vec<OfficerPermission>,
+    pub timestamp: i64,
+}
+
+// Passing None withdraws a pending transfer
+pub fn propose_authority_transfer(
+    ctx: Context<ManageElectionAuthority>,
+    new_authority: Option<Pubkey>
+) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    election.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposed {
+        election: election.key(),
+        authority: election.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn accept_authority_transfer(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let old_authority = election.authority;
+
+    election.authority = ctx.accounts.new_authority.key();
+    election.pending_authority = None;
+
+    emit!(AuthorityTransferred {
+        election: election.key(),
+        old_authority,
+        new_authority: election.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Replaces the officer's permissions; an empty list removes the officer
+pub fn set_election_officer(
+    ctx: Context<ManageElectionAuthority>,
+    officer: Pubkey,
+    permissions: Vec<OfficerPermission>
+) -> Result<()> {
+    let has_duplicates = permissions
+        .iter()
+        .enumerate()
+        .any(|(i, permission)| permissions[..i].contains(permission));
+    require!(
+        permissions.len() <= MAX_OFFICER_PERMISSIONS && !has_duplicates,
+        D21Error::InvalidOfficerPermissions
+    );
+
+    let election = &mut ctx.accounts.election;
+    require!(officer != election.authority, D21Error::InvalidOfficerPermissions);
+
+    election.officers.retain(|existing| existing.key != officer);
+    if !permissions.is_empty() {
+        require!(election.officers.len() < MAX_OFFICERS, D21Error::TooManyOfficers);
+        election.officers.push(Officer {
+            key: officer,
+            permissions: permissions.clone(),
+        });
+    }
+
+    emit!(OfficerUpdated {
+        election: election.key(),
+        officer,
+        permissions,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
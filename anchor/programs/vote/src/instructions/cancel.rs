@@ -0,0 +1,66 @@
+// instructions/cancel.rs
+
+use anchor_lang::prelude::*;
+use crate::{
+    constant::MAX_REASON_LENGTH,
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionStatus,
+    OfficerPermission,
+};
+
+#[derive(Accounts)]
+pub struct CancelElection<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.can_act(&authority.key(), &OfficerPermission::CancelElection) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+}
+
+#[event]
+pub struct ElectionCancelled {
+    pub election: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+pub fn cancel_election(ctx: Context<CancelElection>, reason: String) -> Result<()> {
+    apply_cancel(&mut ctx.accounts.election, reason, Clock::get()?.unix_timestamp)
+}
+
+pub(crate) fn validate_reason(reason: &str) -> Result<()> {
+    require!(
+        !reason.is_empty() && reason.len() <= MAX_REASON_LENGTH,
+        D21Error::InvalidReasonLength
+    );
+    Ok(())
+}
+
+// Shared with committee proposals
+pub(crate) fn apply_cancel(
+    election: &mut Account<Election>,
+    reason: String,
+    now: i64
+) -> Result<()> {
+    validate_reason(&reason)?;
+
+    // Cancelled is terminal: no winners are recorded
+    election.status = ElectionStatus::Cancelled;
+    election.end_time = Some(now);
+
+    emit!(ElectionCancelled {
+        election: election.key(),
+        reason,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// instructions/change_vote.rs
+
+use anchor_lang::prelude::*;
+use crate::{
+    Ballot,
+    D21Error,
+    Election,
+    ElectionVoter,
+    UserVerification,
+    VerificationError,
+    VoterError,
+    VoterStatus,
+};
+
+#[derive(Accounts)]
+pub struct ChangeVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive,
+        constraint = election.allow_revote @ D21Error::RevoteNotAllowed
+    )]
+    pub election: Account<'info, Election>,
+
+    // Not needed by voters registered through an allowlist proof or put on the roll
+    // by the authority
+    #[account(
+        seeds = [b"user_verification", election.organization.as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = user_verification.is_verified @ VerificationError::VoterNotVerified,
+    )]
+    pub user_verification: Option<Account<'info, UserVerification>>,
+
+    #[account(
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.is_vouched_for(&election, user_verification.is_some()) @ VerificationError::VoterNotVerified,
+        constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = election_voter.is_eligible @ VoterError::VoterNotEligible,
+        constraint = election_voter.has_voted @ VoterError::BallotNotCast,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"ballot", election.key().as_ref(), voter.key().as_ref()],
+        bump = ballot.bump,
+        constraint = !ballot.voided @ D21Error::BallotVoided,
+    )]
+    pub ballot: Account<'info, Ballot>,
+}
+
+#[event]
+pub struct VoteChanged {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_vote_change(
+    ctx: Context<ChangeVote>,
+    plus_votes: Vec<u8>,
+    minus_votes: Vec<u8>
+) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let ballot = &mut ctx.accounts.ballot;
+
+    // Ballots can only be revised while voting is open
+    let now = Clock::get()?.unix_timestamp;
+    election.check_voting_window(now)?;
+    election.sync_status(now);
+
+    require!(!election.secret_ballot, D21Error::SecretBallotRequired);
+
+    // Validate the new selection before touching the counters
+    election.validate_ballot(&plus_votes, &minus_votes)?;
+
+    // Swap the old selection for the new one
+    // The ballot keeps the voting weight it was cast with
+    election.retract_ballot(&ballot.plus_votes, &ballot.minus_votes, ballot.weight)?;
+    election.apply_ballot(&plus_votes, &minus_votes, ballot.weight)?;
+
+    // Rewrite ballot
+    ballot.plus_votes = plus_votes;
+    ballot.minus_votes = minus_votes;
+
+    emit!(VoteChanged {
+        election: election.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn process_commitment_change(ctx: Context<ChangeVote>, commitment: [u8; 32]) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let ballot = &mut ctx.accounts.ballot;
+
+    // Commitments can only be replaced while voting is open
+    let now = Clock::get()?.unix_timestamp;
+    election.check_voting_window(now)?;
+    election.sync_status(now);
+
+    require!(election.secret_ballot, D21Error::NotSecretBallot);
+
+    ballot.commitment = Some(commitment);
+
+    emit!(VoteChanged {
+        election: election.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+// instructions/close.rs
+
+use anchor_lang::prelude::*;
+use crate::{
+    return_sponsored_rent,
+    Ballot,
+    D21Error,
+    Election,
+    ElectionTombstone,
+    ElectionVoter,
+    NftClaim,
+    Proposal,
+    Sponsorship,
+    Tally,
+    VoterError,
+};
+
+// Closing leaves an ElectionTombstone at the election's address instead of
+// freeing it; the handler checks the election and refunds the remaining rent
+#[derive(Accounts)]
+pub struct CloseElection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: deserialized and validated in the handler, which rewrites it in place
+    #[account(mut, owner = crate::ID)]
+    pub election: UncheckedAccount<'info>,
+
+    /// CHECK: the election's sponsorship vault, which must never have existed
+    /// or already be closed, so its funds can't be stranded
+    #[account(
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump,
+        constraint = sponsorship.data_is_empty() @ D21Error::SponsorshipStillOpen
+    )]
+    pub sponsorship: UncheckedAccount<'info>,
+}
+
+// Whatever the sponsorship vault has left goes back to the authority; it has
+// to be closed before the election itself
+#[derive(Accounts)]
+pub struct CloseSponsorship<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
+        constraint = election.status.is_terminal() @ D21Error::ElectionNotFinal
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+}
+
+// The voter closes their own accounts; the rent goes back to whoever paid it,
+// less any part the sponsorship covered, which goes back to the vault
+#[derive(Accounts)]
+pub struct CloseElectionVoter<'info> {
+    pub voter: Signer<'info>,
+
+    /// CHECK: only receives the rent refund
+    #[account(mut, address = election_voter.payer @ D21Error::InvalidRentRecipient)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.deposited_amount == 0 @ VoterError::TokensStillDeposited,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    /// CHECK: the election's sponsorship vault, which may never have existed or
+    /// already be closed; validated in the handler
+    #[account(mut, seeds = [b"sponsorship", election.key().as_ref()], bump)]
+    pub sponsorship: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBallot<'info> {
+    pub voter: Signer<'info>,
+
+    /// CHECK: only receives the rent refund
+    #[account(mut, address = ballot.payer @ D21Error::InvalidRentRecipient)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"ballot", election.key().as_ref(), voter.key().as_ref()],
+        bump = ballot.bump,
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    /// CHECK: the election's sponsorship vault, which may never have existed or
+    /// already be closed; validated in the handler
+    #[account(mut, seeds = [b"sponsorship", election.key().as_ref()], bump)]
+    pub sponsorship: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTally<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"tally", election.key().as_ref()],
+        bump = tally.bump,
+        has_one = payer,
+    )]
+    pub tally: Account<'info, Tally>,
+}
+
+#[derive(Accounts)]
+pub struct CloseNftClaim<'info> {
+    pub voter: Signer<'info>,
+
+    /// CHECK: only receives the rent refund
+    #[account(mut, address = nft_claim.payer @ D21Error::InvalidRentRecipient)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"nft_claim", election.key().as_ref(), nft_claim.mint.as_ref()],
+        bump = nft_claim.bump,
+        has_one = voter,
+    )]
+    pub nft_claim: Account<'info, NftClaim>,
+
+    /// CHECK: the election's sponsorship vault, which may never have existed or
+    /// already be closed; validated in the handler
+    #[account(mut, seeds = [b"sponsorship", election.key().as_ref()], bump)]
+    pub sponsorship: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"proposal", election.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = proposer,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[event]
+pub struct ElectionClosed {
+    pub election: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn close_election_account(ctx: Context<CloseElection>) -> Result<()> {
+    let info = ctx.accounts.election.to_account_info();
+    let election = Election::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+
+    let address = Pubkey::create_program_address(
+        &[b"election", election.organization.as_ref(), election.id.as_bytes(), &[election.bump]],
+        ctx.program_id
+    ).map_err(|_| ErrorCode::ConstraintSeeds)?;
+    require_keys_eq!(address, info.key(), ErrorCode::ConstraintSeeds);
+    require_keys_eq!(ctx.accounts.authority.key(), election.authority, D21Error::Unauthorized);
+    require!(election.status.is_terminal(), D21Error::ElectionNotFinal);
+
+    // Shrink the account to a tombstone so the id stays taken
+    let now = Clock::get()?.unix_timestamp;
+    let tombstone = ElectionTombstone {
+        organization: election.organization,
+        authority: election.authority,
+        id: election.id,
+        closed_at: now,
+    };
+    info.realloc(ElectionTombstone::SPACE, false)?;
+    {
+        let mut data = info.try_borrow_mut_data()?;
+        data.fill(0);
+        tombstone.try_serialize(&mut &mut data[..])?;
+    }
+
+    // Everything above the tombstone's own rent goes back to the authority
+    let refund = info.lamports() - Rent::get()?.minimum_balance(ElectionTombstone::SPACE);
+    **info.try_borrow_mut_lamports()? -= refund;
+    **ctx.accounts.authority.try_borrow_mut_lamports()? += refund;
+
+    emit!(ElectionClosed {
+        election: info.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn close_sponsorship_account(_ctx: Context<CloseSponsorship>) -> Result<()> {
+    // Remaining lamports are returned to the authority by the close constraint
+    Ok(())
+}
+
+pub fn close_election_voter_account(ctx: Context<CloseElectionVoter>) -> Result<()> {
+    require!(
+        Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::ElectionNotFinal
+    );
+    return_sponsored_rent(
+        &ctx.accounts.election.key(),
+        &ctx.accounts.election_voter.to_account_info(),
+        &ctx.accounts.sponsorship,
+        ctx.accounts.election_voter.sponsored_rent
+    )
+}
+
+pub fn close_ballot_account(ctx: Context<CloseBallot>) -> Result<()> {
+    require!(
+        Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::ElectionNotFinal
+    );
+    return_sponsored_rent(
+        &ctx.accounts.election.key(),
+        &ctx.accounts.ballot.to_account_info(),
+        &ctx.accounts.sponsorship,
+        ctx.accounts.ballot.sponsored_rent
+    )
+}
+
+pub fn close_tally_account(ctx: Context<CloseTally>) -> Result<()> {
+    require!(
+        Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::ElectionNotFinal
+    );
+    Ok(())
+}
+
+pub fn close_nft_claim_account(ctx: Context<CloseNftClaim>) -> Result<()> {
+    require!(
+        Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::ElectionNotFinal
+    );
+    return_sponsored_rent(
+        &ctx.accounts.election.key(),
+        &ctx.accounts.nft_claim.to_account_info(),
+        &ctx.accounts.sponsorship,
+        ctx.accounts.nft_claim.sponsored_rent
+    )
+}
+
+// Executed proposals are done with; pending ones lapse once the election is final
+pub fn close_proposal_account(ctx: Context<CloseProposal>) -> Result<()> {
+    require!(
+        ctx.accounts.proposal.executed || Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::ElectionNotFinal
+    );
+    Ok(())
+}
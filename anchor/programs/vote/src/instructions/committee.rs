@@ -0,0 +1,329 @@
+// instructions/committee.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{ MAX_COMMITTEE_MEMBERS, MAX_PROPOSAL_LIFETIME },
+    AdminAction,
+    Ballot,
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionStatus,
+    ElectionVoter,
+    Proposal,
+};
+use super::{
+    apply_cancel,
+    apply_election_status,
+    apply_end,
+    apply_void_ballot,
+    apply_voter_status,
+    validate_reason,
+    StatusRecorder,
+};
+
+#[derive(Accounts)]
+pub struct SetCommittee<'info> {
+    pub authority: Signer<'info>,
+
+    // Once a committee is set the authority can no longer act alone, so it is
+    // fixed before registration opens like the eligibility rules
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
+        constraint = election.status == ElectionStatus::Created @ CommitteeError::CommitteeLocked
+    )]
+    pub election: Account<'info, Election>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.committee.is_empty() @ CommitteeError::NoCommittee,
+        constraint = election.is_committee_member(&proposer.key()) @ CommitteeError::NotCommitteeMember,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", election.key().as_ref(), &election.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.is_committee_member(&member.key()) @ CommitteeError::NotCommitteeMember
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", election.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = election,
+        constraint = !proposal.executed @ CommitteeError::ProposalExecuted,
+        constraint = !proposal.has_approved(&member.key()) @ CommitteeError::AlreadyApproved
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+// Anyone can execute a proposal once it has enough approvals. The payer
+// covers the rent of any status history the proposal grows, and VoidBallot
+// proposals take the voter's delegation tree as remaining accounts.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", election.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = election,
+        constraint = !proposal.executed @ CommitteeError::ProposalExecuted,
+        constraint = proposal.approvals.len() >= (election.approval_threshold as usize) @ CommitteeError::ThresholdNotMet
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    // Required for UpdateVoterStatus and VoidBallot proposals
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), election_voter.voter.as_ref()],
+        bump = election_voter.bump
+    )]
+    pub election_voter: Option<Account<'info, ElectionVoter>>,
+
+    // Required for VoidBallot proposals
+    #[account(
+        mut,
+        seeds = [b"ballot", election.key().as_ref(), ballot.voter.as_ref()],
+        bump = ballot.bump,
+        constraint = !ballot.voided @ D21Error::BallotVoided
+    )]
+    pub ballot: Option<Account<'info, Ballot>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CommitteeSet {
+    pub election: Pubkey,
+    pub committee: Vec<Pubkey>,
+    pub approval_threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionProposed {
+    pub election: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub election: Pubkey,
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub election: Pubkey,
+    pub proposal: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn set_election_committee(
+    ctx: Context<SetCommittee>,
+    committee: Vec<Pubkey>,
+    approval_threshold: u8
+) -> Result<()> {
+    let mut unique_members = committee.clone();
+    unique_members.sort();
+    unique_members.dedup();
+    require!(
+        !committee.is_empty() &&
+            committee.len() <= MAX_COMMITTEE_MEMBERS &&
+            unique_members.len() == committee.len(),
+        CommitteeError::InvalidCommittee
+    );
+    require!(
+        approval_threshold > 0 && (approval_threshold as usize) <= committee.len(),
+        CommitteeError::InvalidCommittee
+    );
+
+    let election = &mut ctx.accounts.election;
+    election.committee = committee.clone();
+    election.approval_threshold = approval_threshold;
+
+    emit!(CommitteeSet {
+        election: election.key(),
+        committee,
+        approval_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn propose_admin_action(
+    ctx: Context<ProposeAction>,
+    action: AdminAction,
+    expires_at: i64
+) -> Result<()> {
+    if let AdminAction::CancelElection(reason) = &action {
+        validate_reason(reason)?;
+    }
+
+    let election = &mut ctx.accounts.election;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at > now && expires_at - now <= MAX_PROPOSAL_LIFETIME,
+        CommitteeError::InvalidProposalExpiry
+    );
+
+    // Proposing counts as the proposer's approval
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.election = election.key();
+    proposal.index = election.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action.clone();
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.executed = false;
+    proposal.created_at = now;
+    proposal.expires_at = expires_at;
+    proposal.bump = ctx.bumps.proposal;
+
+    election.proposal_count += 1;
+
+    emit!(ActionProposed {
+        election: election.key(),
+        proposal: proposal.key(),
+        proposer: proposal.proposer,
+        action,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn approve_admin_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!proposal.is_expired(now), CommitteeError::ProposalExpired);
+
+    proposal.approvals.push(ctx.accounts.member.key());
+
+    emit!(ProposalApproved {
+        election: ctx.accounts.election.key(),
+        proposal: proposal.key(),
+        member: ctx.accounts.member.key(),
+        approvals: proposal.approvals.len() as u8,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn execute_admin_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>
+) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!proposal.is_expired(now), CommitteeError::ProposalExpired);
+
+    // Status changes are attributed to the proposal, not whoever executes it
+    let recorder = StatusRecorder {
+        changed_by: proposal.key(),
+        payer: &ctx.accounts.payer,
+        system_program: &ctx.accounts.system_program,
+        now,
+    };
+
+    match proposal.action.clone() {
+        AdminAction::UpdateElectionStatus(new_status) => {
+            apply_election_status(election, new_status, now)?;
+        }
+        AdminAction::EndElection => {
+            apply_end(election, now)?;
+        }
+        AdminAction::CancelElection(reason) => {
+            apply_cancel(election, reason, now)?;
+        }
+        AdminAction::UpdateVoterStatus { voter, new_status, reason } => {
+            let election_voter = ctx.accounts.election_voter
+                .as_mut()
+                .filter(|election_voter| election_voter.voter == voter)
+                .ok_or(CommitteeError::ProposalVoterMismatch)?;
+            apply_voter_status(
+                election,
+                election_voter,
+                new_status,
+                reason,
+                &recorder
+            )?;
+        }
+        AdminAction::VoidBallot { voter, reason } => {
+            let election_voter = ctx.accounts.election_voter
+                .as_mut()
+                .filter(|election_voter| election_voter.voter == voter)
+                .ok_or(CommitteeError::ProposalVoterMismatch)?;
+            let ballot = ctx.accounts.ballot
+                .as_mut()
+                .filter(|ballot| ballot.voter == voter)
+                .ok_or(CommitteeError::ProposalVoterMismatch)?;
+            apply_void_ballot(
+                election,
+                election_voter,
+                ballot,
+                reason,
+                ctx.remaining_accounts,
+                &recorder
+            )?;
+        }
+    }
+
+    proposal.executed = true;
+
+    emit!(ProposalExecuted {
+        election: election.key(),
+        proposal: proposal.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
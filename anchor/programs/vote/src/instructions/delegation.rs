@@ -0,0 +1,306 @@
+// instructions/delegation.rs
+use anchor_lang::prelude::*;
+
+use crate::{ D21Error, Election, ElectionVoter, VoterError, VoterStatus, VotingMethod };
+
+#[derive(Accounts)]
+pub struct DelegateVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive,
+        constraint = election.voting_method != VotingMethod::Quadratic @ VoterError::DelegationNotSupported
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = election_voter.is_eligible @ VoterError::VoterNotEligible,
+        constraint = !election_voter.has_voted @ D21Error::AlreadyVoted,
+        constraint = election_voter.delegate.is_none() @ VoterError::AlreadyDelegated,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), delegate_voter.voter.as_ref()],
+        bump = delegate_voter.bump,
+        constraint = delegate_voter.voter != voter.key() @ VoterError::SelfDelegation,
+        constraint = delegate_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = !delegate_voter.has_voted @ VoterError::DelegateAlreadyVoted,
+    )]
+    pub delegate_voter: Account<'info, ElectionVoter>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    // Once the delegate has voted the delegator is marked as voted and stays bound.
+    // The remaining accounts carry the delegate's chain, as for delegate_vote
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.delegate.is_some() @ VoterError::NotDelegated,
+        constraint = !election_voter.has_voted @ D21Error::AlreadyVoted,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), delegate_voter.voter.as_ref()],
+        bump = delegate_voter.bump,
+        constraint = election_voter.delegate == Some(delegate_voter.voter) @ VoterError::DelegationChainBroken,
+    )]
+    pub delegate_voter: Account<'info, ElectionVoter>,
+}
+
+#[event]
+pub struct VoteDelegated {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegatedVotesCast {
+    pub election: Pubkey,
+    pub delegate: Pubkey,
+    pub delegators: u32,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+// The remaining accounts carry the ElectionVoter accounts further along the
+// delegate's own chain, so a delegation that loops back to the voter is refused.
+// The voter brings its own delegators along, and every voter up to the end of
+// the chain takes them on within MAX_DELEGATORS.
+pub fn delegate_election_vote(ctx: Context<DelegateVote>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.election.has_ended(now), D21Error::ElectionNotActive);
+
+    let election_key = ctx.accounts.election.key();
+    let voter = ctx.accounts.voter.key();
+    let moved = ctx.accounts.election_voter.total_delegators
+        .checked_add(1)
+        .ok_or(VoterError::TooManyDelegators)?;
+
+    update_chain(
+        ctx.accounts.delegate_voter.delegate,
+        voter,
+        ctx.remaining_accounts,
+        &election_key,
+        |hop| {
+            require!(!hop.has_voted, VoterError::DelegateAlreadyVoted);
+            hop.add_delegators(moved)
+        }
+    )?;
+
+    ctx.accounts.election_voter.delegate = Some(ctx.accounts.delegate_voter.voter);
+    ctx.accounts.delegate_voter.delegator_count += 1;
+    ctx.accounts.delegate_voter.add_delegators(moved)?;
+
+    emit!(VoteDelegated {
+        election: election_key,
+        voter,
+        delegate: ctx.accounts.delegate_voter.voter,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_election_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+    let moved = ctx.accounts.election_voter.total_delegators.saturating_add(1);
+    update_chain(
+        ctx.accounts.delegate_voter.delegate,
+        ctx.accounts.voter.key(),
+        ctx.remaining_accounts,
+        &ctx.accounts.election.key(),
+        |hop| {
+            hop.remove_delegators(moved);
+            Ok(())
+        }
+    )?;
+
+    ctx.accounts.election_voter.delegate = None;
+    ctx.accounts.delegate_voter.delegator_count -= 1;
+    ctx.accounts.delegate_voter.remove_delegators(moved);
+
+    emit!(DelegationRevoked {
+        election: ctx.accounts.election.key(),
+        voter: ctx.accounts.voter.key(),
+        delegate: ctx.accounts.delegate_voter.voter,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Resolves every delegation that ends at `delegate` from the ElectionVoter
+// accounts passed alongside its ballot, marks those delegators as voted and
+// returns their combined weight. Delegation keeps the tree within
+// MAX_DELEGATORS, so it always fits beside the ballot.
+pub(crate) fn apply_delegations(
+    election: &Account<Election>,
+    delegate: &ElectionVoter,
+    accounts: &[AccountInfo],
+    now: i64
+) -> Result<u64> {
+    if delegate.delegator_count == 0 {
+        return Ok(0);
+    }
+
+    let election_key = election.key();
+    let delegators = load_delegation_tree(&election_key, delegate, accounts)?;
+
+    // Suspended or ineligible delegators stay in the tree but add no weight
+    let mut weight: u64 = 0;
+    let mut counted = 0;
+    for (info, mut delegator) in accounts.iter().zip(delegators) {
+        if delegator.status != VoterStatus::Active || !delegator.is_eligible {
+            continue;
+        }
+
+        weight = weight
+            .checked_add(
+                if election.vote_mint.is_some() {
+                    delegator.deposited_amount
+                } else {
+                    delegator.voting_power
+                }
+            )
+            .ok_or(D21Error::VoteCountOverflow)?;
+        counted += 1;
+
+        delegator.has_voted = true;
+        delegator.voted_through = Some(delegate.voter);
+        delegator.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    emit!(DelegatedVotesCast {
+        election: election_key,
+        delegate: delegate.voter,
+        delegators: counted,
+        weight,
+        timestamp: now,
+    });
+
+    Ok(weight)
+}
+
+// Loads the ElectionVoter accounts of every delegation that ends at
+// `delegate`, in the order supplied. The whole tree must be there: each voter
+// in it is checked against its delegator_count.
+pub(crate) fn load_delegation_tree(
+    election: &Pubkey,
+    delegate: &ElectionVoter,
+    accounts: &[AccountInfo]
+) -> Result<Vec<ElectionVoter>> {
+    let delegators = accounts
+        .iter()
+        .map(|info| load_election_voter(info, election))
+        .collect::<Result<Vec<_>>>()?;
+
+    for (i, info) in accounts.iter().enumerate() {
+        require!(
+            info.is_writable && !accounts[..i].iter().any(|other| other.key == info.key),
+            VoterError::DelegationChainBroken
+        );
+    }
+
+    // Every supplied voter must reach the delegate by following its chain
+    for delegator in &delegators {
+        let mut next = delegator.delegate;
+        let mut hops = 0;
+        loop {
+            let key = next.ok_or(VoterError::DelegationChainBroken)?;
+            if key == delegate.voter {
+                break;
+            }
+            require!(hops < delegators.len(), VoterError::DelegationChainBroken);
+            next = delegators
+                .iter()
+                .find(|hop| hop.voter == key)
+                .ok_or(VoterError::DelegationChainBroken)?.delegate;
+            hops += 1;
+        }
+    }
+
+    // No voter in the tree may be left out
+    for voter in std::iter::once(delegate).chain(delegators.iter()) {
+        let supplied = delegators
+            .iter()
+            .filter(|delegator| delegator.delegate == Some(voter.voter))
+            .count();
+        require!(supplied == (voter.delegator_count as usize), VoterError::DelegatorsMissing);
+    }
+
+    Ok(delegators)
+}
+
+// Follows a delegation chain from `next` to its end through the supplied
+// ElectionVoter accounts, applying `update` to each hop and writing it back.
+// Every hop must be supplied and writable.
+fn update_chain(
+    mut next: Option<Pubkey>,
+    voter: Pubkey,
+    accounts: &[AccountInfo],
+    election: &Pubkey,
+    mut update: impl FnMut(&mut ElectionVoter) -> Result<()>
+) -> Result<()> {
+    let mut chain = accounts
+        .iter()
+        .map(|info| load_election_voter(info, election))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut hops = 0;
+    while let Some(key) = next {
+        require!(key != voter, VoterError::DelegationCycle);
+        require!(hops < chain.len(), VoterError::DelegationChainBroken);
+
+        let index = chain
+            .iter()
+            .position(|hop| hop.voter == key)
+            .ok_or(VoterError::DelegationChainBroken)?;
+        let info = &accounts[index];
+        require!(info.is_writable, VoterError::DelegationChainBroken);
+
+        let hop = &mut chain[index];
+        update(hop)?;
+        hop.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        next = hop.delegate;
+        hops += 1;
+    }
+    Ok(())
+}
+
+fn load_election_voter(info: &AccountInfo, election: &Pubkey) -> Result<ElectionVoter> {
+    require!(info.owner == &crate::ID, VoterError::DelegationChainBroken);
+    let election_voter = ElectionVoter::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(election_voter.election == *election, VoterError::DelegationChainBroken);
+    Ok(election_voter)
+}
@@ -1,7 +1,14 @@
 // instructions/end.rs
 
 use anchor_lang::prelude::*;
-use crate::{ D21Error, Election, ElectionStatus };
+use crate::{
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionFinalized,
+    ElectionStatus,
+    OfficerPermission,
+};
 
 #[derive(Accounts)]
 pub struct EndElection<'info> {
@@ -10,44 +17,39 @@ pub struct EndElection<'info> {
 
     #[account(
         mut,
-        seeds = [b"election", election.authority.key().as_ref(), election.id.as_bytes()],
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
         bump = election.bump,
-        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
-        constraint = election.status == ElectionStatus::Active @ D21Error::ElectionNotActive
+        constraint = election.can_act(&authority.key(), &OfficerPermission::EndElection) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
     )]
     pub election: Account<'info, Election>,
 }
 
 pub fn end_election(ctx: Context<EndElection>) -> Result<()> {
-    let election = &mut ctx.accounts.election;
-    
+    apply_end(&mut ctx.accounts.election, Clock::get()?.unix_timestamp)
+}
+
+// Shared with committee proposals
+pub(crate) fn apply_end(election: &mut Account<Election>, now: i64) -> Result<()> {
+    // Results can only be produced once ballots have been accepted (and revealed)
+    election.sync_status(now);
+    require!(election.can_tally(), D21Error::ElectionNotActive);
+    require!(!election.voting_method.uses_tally(), D21Error::TallyRequired);
+
     // Set end time and status
     election.status = ElectionStatus::Ended;
-    election.end_time = Some(Clock::get()?.unix_timestamp);
-    
+    election.end_time = Some(now);
+
     // Calculate final rankings
-    let mut candidates = election.candidates.clone();
-    candidates.sort_by(|a, b| {
-        let a_score = a.plus_votes - a.minus_votes;
-        let b_score = b.plus_votes - b.minus_votes;
-        
-        // Sort by score (descending) and then by plus votes if tied
-        b_score.cmp(&a_score)
-            .then(b.plus_votes.cmp(&a.plus_votes))
+    election.compute_results();
+
+    emit!(ElectionFinalized {
+        election: election.key(),
+        winners: election.winners.clone(),
+        unrevealed_ballots: election.unrevealed_ballots(),
+        timestamp: now,
     });
-    
-    // Update rankings and set winners
-    for (i, candidate) in candidates.iter().enumerate() {
-        let idx = election.candidates
-            .iter()
-            .position(|c| c.address == candidate.address)
-            .unwrap();
-        election.candidates[idx].rank = i as u8;
-        
-        if i < election.num_winners as usize {
-            election.winners.push(candidate.address);
-        }
-    }
-    
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,205 @@
+// instructions/escrow.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account,
+    transfer_checked,
+    CloseAccount,
+    Mint,
+    TokenAccount,
+    TokenInterface,
+    TransferChecked,
+};
+use crate::{ D21Error, Election, ElectionVoter, VoterStatus, VoterError };
+
+// Tokens are held in a vault owned by the voter's ElectionVoter PDA, so the
+// same tokens can't back ballots from two wallets
+#[derive(Accounts)]
+pub struct DepositTokens<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive,
+        constraint = election.vote_mint.is_some() @ D21Error::NotTokenWeighted
+    )]
+    pub election: Account<'info, Election>,
+
+    // Deposits after voting would not change the ballot, so they are refused
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = !election_voter.has_voted @ D21Error::AlreadyVoted,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        constraint = Some(mint.key()) == election.vote_mint @ D21Error::InvalidVoteTokenAccount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = voter,
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        seeds = [b"vault", election_voter.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = election_voter,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokens<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// CHECK: may already be closed by the authority; validated in the handler
+    pub election: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(address = vault.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = voter,
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", election_voter.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TokensDeposited {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensWithdrawn {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn deposit_voting_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, D21Error::NoVotingPower);
+
+    transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), TransferChecked {
+            from: ctx.accounts.voter_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.voter.to_account_info(),
+        }),
+        amount,
+        ctx.accounts.mint.decimals
+    )?;
+
+    let election_voter = &mut ctx.accounts.election_voter;
+    election_voter.deposited_amount += amount;
+
+    emit!(TokensDeposited {
+        election: ctx.accounts.election.key(),
+        voter: ctx.accounts.voter.key(),
+        amount,
+        total_deposited: election_voter.deposited_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_voting_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
+    // Tokens behind a cast ballot stay locked until the election is over
+    require!(
+        !ctx.accounts.election_voter.has_voted ||
+            Election::is_closed_or_final(&ctx.accounts.election)?,
+        D21Error::TokensLocked
+    );
+
+    let election_key = ctx.accounts.election.key();
+    let voter_key = ctx.accounts.voter.key();
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[
+            b"election_voter",
+            election_key.as_ref(),
+            voter_key.as_ref(),
+            &[ctx.accounts.election_voter.bump],
+        ],
+    ];
+
+    let amount = ctx.accounts.vault.amount;
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.election_voter.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount,
+        ctx.accounts.mint.decimals
+    )?;
+
+    // Return the vault's rent along with the tokens
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.voter.to_account_info(),
+                authority: ctx.accounts.election_voter.to_account_info(),
+            },
+            signer_seeds
+        )
+    )?;
+
+    ctx.accounts.election_voter.deposited_amount = 0;
+
+    emit!(TokensWithdrawn {
+        election: election_key,
+        voter: voter_key,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
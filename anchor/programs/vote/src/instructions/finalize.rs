@@ -0,0 +1,50 @@
+// instructions/finalize.rs
+
+use anchor_lang::prelude::*;
+use crate::{ D21Error, Election, ElectionStatus };
+
+// Permissionless: anyone may crank an election to Ended once its end time
+// (plus the reveal period for secret ballots) has passed
+#[derive(Accounts)]
+pub struct FinalizeElection<'info> {
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+}
+
+#[event]
+pub struct ElectionFinalized {
+    pub election: Pubkey,
+    pub winners: Vec<Pubkey>,
+    // Secret-ballot commitments that were never opened and so are not counted
+    pub unrevealed_ballots: u32,
+    pub timestamp: i64,
+}
+
+pub fn finalize_election(ctx: Context<FinalizeElection>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Only elections with a scheduled end can be finalized without the authority
+    require!(election.end_time.is_some(), D21Error::EndTimeNotSet);
+    require!(election.results_due(now), D21Error::ElectionStillRunning);
+    require!(!election.voting_method.uses_tally(), D21Error::TallyRequired);
+
+    election.status = ElectionStatus::Ended;
+
+    // Calculate final rankings
+    election.compute_results();
+
+    emit!(ElectionFinalized {
+        election: election.key(),
+        winners: election.winners.clone(),
+        unrevealed_ballots: election.unrevealed_ballots(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
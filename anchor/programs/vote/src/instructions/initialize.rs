@@ -1,30 +1,52 @@
 // instructions/initialize.rs
 use anchor_lang::prelude::*;
-use crate::{ state::{ Candidate, Election, ElectionStatus, UserType }, D21Error };
+use crate::{
+    state::{
+        Candidate,
+        CategoryRegistry,
+        Election,
+        ElectionConfig,
+        ElectionStatus,
+        EligibilityRule,
+        RuleLogic,
+        VotingMethod,
+    },
+    D21Error,
+    Organization,
+    OrganizationError,
+    MAX_ALLOWED_VOTER_TYPES,
+};
 
 #[derive(Accounts)]
-#[instruction(
-    election_id: String,
-    name: String,
-    candidates: Vec<Pubkey>,
-    num_winners: u8,
-    num_plus_votes: u8,
-    num_minus_votes: u8,
-    allowed_voter_types: Vec<UserType>
-)]
+#[instruction(election_id: String, name: String, candidates: Vec<Pubkey>)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    // Election IDs only need to be unique within the organisation
+    #[account(
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump,
+        constraint = organization.can_create_elections(&authority.key()) @ OrganizationError::NotElectionCreator
+    )]
+    pub organization: Account<'info, Organization>,
+
     #[account(
         init,
         payer = authority,
         space = Election::space(candidates.len()),
-        seeds = [b"election", authority.key().as_ref(), election_id.as_bytes()],
+        seeds = [b"election", organization.key().as_ref(), election_id.as_bytes()],
         bump
     )]
     pub election: Account<'info, Election>,
 
+    // Allowed voter types must be categories the organisation currently offers
+    #[account(
+        seeds = [b"categories", organization.key().as_ref()],
+        bump = category_registry.bump
+    )]
+    pub category_registry: Account<'info, CategoryRegistry>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -33,29 +55,81 @@ pub fn initialize_election(
     election_id: String,
     name: String,
     candidates: Vec<Pubkey>,
-    num_winners: u8,
-    num_plus_votes: u8,
-    num_minus_votes: u8,
-    allowed_voter_types: Vec<UserType>
+    config: ElectionConfig
 ) -> Result<()> {
     require!(!election_id.is_empty() && election_id.len() <= 32, D21Error::InvalidElectionId);
-    require!(!allowed_voter_types.is_empty(), D21Error::InvalidVoterTypes);
+    require!(
+        !config.allowed_voter_types.is_empty() &&
+            config.allowed_voter_types.len() <= MAX_ALLOWED_VOTER_TYPES,
+        D21Error::InvalidVoterTypes
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    Election::validate_schedule(config.start_time, config.end_time, now)?;
 
     let election = &mut ctx.accounts.election;
 
     // Validate configuration
-    election.validate_config(&name, &candidates, num_winners, num_plus_votes, num_minus_votes)?;
+    election.validate_config(&name, &candidates, &config)?;
+    let ElectionConfig {
+        num_winners,
+        num_plus_votes,
+        num_minus_votes,
+        allowed_voter_types,
+        start_time,
+        end_time,
+        allow_revote,
+        secret_ballot,
+        voting_method,
+        credit_budget,
+        vote_mint,
+        nft_collection,
+        one_vote_per_nft,
+        voter_merkle_root,
+    } = config;
+
+    // Registration is gated by an NFT collection or an allowlist, never both
+    require!(
+        nft_collection.is_none() || voter_merkle_root.is_none(),
+        D21Error::ConflictingEligibilityRules
+    );
+
+    // Quadratic ballots are not handled by commit/reveal, change_vote or token weighting
+    if voting_method == VotingMethod::Quadratic {
+        require!(
+            !secret_ballot && !allow_revote && vote_mint.is_none(),
+            D21Error::UnsupportedMethodOption
+        );
+    }
 
     // Set admin data
+    election.organization = ctx.accounts.organization.key();
     election.authority = ctx.accounts.authority.key();
     election.bump = ctx.bumps.election;
+    election.pending_authority = None;
+    election.officers = vec![];
+    election.committee = vec![];
+    election.approval_threshold = 0;
+    election.proposal_count = 0;
 
     // Set configuration
     election.id = election_id;
     election.name = name;
+    election.voting_method = voting_method;
     election.num_winners = num_winners;
     election.num_plus_votes = num_plus_votes;
     election.num_minus_votes = num_minus_votes;
+    election.allow_revote = allow_revote;
+    election.secret_ballot = secret_ballot;
+    election.credit_budget = credit_budget;
+    election.vote_mint = vote_mint;
+    election.nft_collection = nft_collection;
+    election.one_vote_per_nft = one_vote_per_nft && nft_collection.is_some();
+    election.voter_merkle_root = voter_merkle_root;
+    election.eligibility_logic = RuleLogic::All;
+    let voter_types = EligibilityRule::UserType(allowed_voter_types.clone());
+    voter_types.validate(&ctx.accounts.category_registry)?;
+    election.eligibility_rules = vec![voter_types];
     election.allowed_voter_types = allowed_voter_types;
 
     // Initialize candidates
@@ -69,11 +143,13 @@ pub fn initialize_election(
         })
         .collect();
 
-    // Set initial state
-    election.status = ElectionStatus::Active;
-    election.start_time = Clock::get()?.unix_timestamp;
-    election.end_time = None;
+    // Set initial state; the authority opens registration once setup is done
+    election.status = ElectionStatus::Created;
+    election.start_time = start_time;
+    election.end_time = end_time;
     election.total_voters = 0;
+    election.total_weight = 0;
+    election.revealed_ballots = 0;
     election.winners = vec![];
 
     Ok(())
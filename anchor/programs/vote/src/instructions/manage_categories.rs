@@ -0,0 +1,140 @@
+// instructions/manage_categories.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{ MAX_CATEGORIES, MAX_CATEGORY_NAME_LENGTH },
+    CategoryRegistry,
+    Organization,
+    OrganizationError,
+    VerificationError,
+    VoterCategory,
+};
+
+// Voter categories are managed by the organisation's admins, so new ones can
+// be added without redeploying the program
+#[derive(Accounts)]
+pub struct InitializeCategories<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump,
+        constraint = organization.is_admin(&admin.key()) @ OrganizationError::NotOrgAdmin
+    )]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = CategoryRegistry::SPACE,
+        seeds = [b"categories", organization.key().as_ref()],
+        bump
+    )]
+    pub category_registry: Account<'info, CategoryRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageCategories<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump,
+        constraint = organization.is_admin(&admin.key()) @ OrganizationError::NotOrgAdmin
+    )]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [b"categories", organization.key().as_ref()],
+        bump = category_registry.bump
+    )]
+    pub category_registry: Account<'info, CategoryRegistry>,
+}
+
+#[event]
+pub struct CategoryAdded {
+    pub organization: Pubkey,
+    pub id: u16,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CategoryRetired {
+    pub organization: Pubkey,
+    pub id: u16,
+    pub timestamp: i64,
+}
+
+pub fn initialize_category_registry(
+    ctx: Context<InitializeCategories>,
+    names: Vec<String>
+) -> Result<()> {
+    let category_registry = &mut ctx.accounts.category_registry;
+    category_registry.categories = vec![];
+    category_registry.bump = ctx.bumps.category_registry;
+
+    let organization = ctx.accounts.organization.key();
+    for name in names {
+        push_category(category_registry, organization, name)?;
+    }
+
+    Ok(())
+}
+
+pub fn add_voter_category(ctx: Context<ManageCategories>, name: String) -> Result<()> {
+    let organization = ctx.accounts.organization.key();
+    push_category(&mut ctx.accounts.category_registry, organization, name)
+}
+
+// Retired categories can no longer be requested; existing verifications keep their id
+pub fn retire_voter_category(ctx: Context<ManageCategories>, id: u16) -> Result<()> {
+    let category = ctx.accounts.category_registry.categories
+        .get_mut(id as usize)
+        .filter(|category| category.active)
+        .ok_or(VerificationError::UnknownCategory)?;
+    category.active = false;
+
+    emit!(CategoryRetired {
+        organization: ctx.accounts.organization.key(),
+        id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn push_category(
+    category_registry: &mut CategoryRegistry,
+    organization: Pubkey,
+    name: String
+) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_CATEGORY_NAME_LENGTH,
+        VerificationError::InvalidCategoryName
+    );
+    require!(
+        category_registry.categories.len() < MAX_CATEGORIES,
+        VerificationError::TooManyCategories
+    );
+
+    let id = category_registry.next_id();
+    category_registry.categories.push(VoterCategory {
+        id,
+        name: name.clone(),
+        active: true,
+    });
+
+    emit!(CategoryAdded {
+        organization,
+        id,
+        name,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+// instructions/manage_organization.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{ MAX_NAME_LENGTH, MAX_ORG_ID_LENGTH },
+    program::Vote,
+    D21Error,
+    OrgRole,
+    Organization,
+    OrganizationError,
+};
+
+// Organisation ids are first come, first served, so only the program's upgrade
+// authority may claim one and hand it to the institution's first admin
+#[derive(Accounts)]
+#[instruction(org_id: String)]
+pub struct CreateOrganization<'info> {
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Vote>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ OrganizationError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = Organization::SPACE,
+        seeds = [b"organization", org_id.as_bytes()],
+        bump
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOrganization<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump,
+        constraint = organization.is_admin(&admin.key()) @ OrganizationError::NotOrgAdmin
+    )]
+    pub organization: Account<'info, Organization>,
+}
+
+#[event]
+pub struct OrganizationCreated {
+    pub organization: Pubkey,
+    pub id: String,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub organization: Pubkey,
+    pub role: OrgRole,
+    pub member: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub organization: Pubkey,
+    pub role: OrgRole,
+    pub member: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn create_organization_account(
+    ctx: Context<CreateOrganization>,
+    org_id: String,
+    name: String,
+    admin: Pubkey
+) -> Result<()> {
+    require!(
+        !org_id.is_empty() && org_id.len() <= MAX_ORG_ID_LENGTH,
+        OrganizationError::InvalidOrganizationId
+    );
+    require!(!name.is_empty() && name.len() <= MAX_NAME_LENGTH, D21Error::InvalidNameLength);
+
+    // The named admin starts as the only one; registrars and creators are granted later
+    let organization = &mut ctx.accounts.organization;
+    organization.id = org_id.clone();
+    organization.name = name;
+    organization.admins = vec![admin];
+    organization.registrars = vec![];
+    organization.election_creators = vec![];
+    organization.bump = ctx.bumps.organization;
+
+    emit!(OrganizationCreated {
+        organization: organization.key(),
+        id: org_id,
+        admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn grant_organization_role(
+    ctx: Context<ManageOrganization>,
+    role: OrgRole,
+    member: Pubkey
+) -> Result<()> {
+    let organization = &mut ctx.accounts.organization;
+    let (members, max_members) = organization.members_mut(&role);
+
+    require!(!members.contains(&member), OrganizationError::MemberAlreadyHasRole);
+    require!(members.len() < max_members, OrganizationError::TooManyMembers);
+    members.push(member);
+
+    emit!(RoleGranted {
+        organization: organization.key(),
+        role,
+        member,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_organization_role(
+    ctx: Context<ManageOrganization>,
+    role: OrgRole,
+    member: Pubkey
+) -> Result<()> {
+    let organization = &mut ctx.accounts.organization;
+    let (members, _) = organization.members_mut(&role);
+
+    let idx = members
+        .iter()
+        .position(|m| *m == member)
+        .ok_or(OrganizationError::MemberNotFound)?;
+    members.remove(idx);
+
+    // An organisation without admins could never be managed again
+    require!(!organization.admins.is_empty(), OrganizationError::LastAdmin);
+
+    emit!(RoleRevoked {
+        organization: organization.key(),
+        role,
+        member,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,14 +2,52 @@
 
 mod initialize;
 mod vote;
+mod signed_vote;
+mod change_vote;
+mod reveal_vote;
 mod end;
+mod finalize;
+mod cancel;
+mod close;
+mod tally;
+mod escrow;
 mod register_voter_for_election;
+mod voter_roll;
+mod sponsorship;
 mod update_voter_status;
+mod void_ballot;
+mod update_election_status;
+mod set_eligibility_rules;
+mod committee;
+mod authority;
+mod delegation;
 mod verify_user_as_voter;
+mod review_verification;
+mod manage_organization;
+mod manage_categories;
 
 pub use initialize::*;
 pub use vote::*;
+pub use signed_vote::*;
+pub use change_vote::*;
+pub use reveal_vote::*;
 pub use end::*;
+pub use finalize::*;
+pub use cancel::*;
+pub use close::*;
+pub use tally::*;
+pub use escrow::*;
 pub use register_voter_for_election::*;
+pub use voter_roll::*;
+pub use sponsorship::*;
 pub use update_voter_status::*;
+pub use void_ballot::*;
+pub use update_election_status::*;
+pub use set_eligibility_rules::*;
+pub use committee::*;
+pub use authority::*;
+pub use delegation::*;
 pub use verify_user_as_voter::*;
+pub use review_verification::*;
+pub use manage_organization::*;
+pub use manage_categories::*;
@@ -1,64 +1,363 @@
 // instructions/register_voter.rs
 use anchor_lang::prelude::*;
+use anchor_spl::metadata::{ Metadata, MetadataAccount };
+use anchor_spl::token_interface::{ Mint, TokenAccount };
 
-use crate::{ D21Error, Election, ElectionStatus, ElectionVoter, UserVerification, VoterStatus };
+use crate::{
+    allowlist_leaf,
+    verify_merkle_proof,
+    reimburse_rent,
+    Election,
+    EligibilityEvidence,
+    ElectionVoter,
+    NftClaim,
+    Sponsorship,
+    UserVerification,
+    VoterError,
+    VoterStatus,
+};
 
 #[derive(Accounts)]
 #[instruction()]
 pub struct RegisterVoter<'info> {
-    #[account(mut)]
     pub voter: Signer<'info>,
 
-    // The election account must exist and be active
+    // Funds the new accounts; a relayer can pay so the voter needs no SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // The election account must exist; the phase is checked against the clock below
     #[account(
         mut,
-        constraint = election.status == ElectionStatus::Active @ crate::error::D21Error::ElectionNotActive
+        constraint = election.nft_collection.is_none() @ VoterError::MembershipNftRequired,
+        constraint = election.voter_merkle_root.is_none() @ VoterError::AllowlistProofRequired
     )]
     pub election: Account<'info, Election>,
 
-    // Verify that the user is verified before allowing registration
+    // Verify that the user is verified before allowing registration; the
+    // election's eligibility rules are checked against it in the handler
     #[account(
-        seeds = [b"user_verification", voter.key().as_ref()],
+        seeds = [b"user_verification", election.organization.as_ref(), voter.key().as_ref()],
         bump,
-        constraint = user_verification.is_verified @ crate::error::VerificationError::VoterNotVerified,
-        constraint = election.is_voter_type_allowed(&user_verification.user_type) @ D21Error::UserTypeNotAllowed
+        constraint = user_verification.is_verified @ crate::error::VerificationError::VoterNotVerified
     )]
     pub user_verification: Account<'info, UserVerification>,
 
     // Initialize the election-specific voter account
     #[account(
         init,
-        payer = voter,
+        payer = payer,
         space = ElectionVoter::SPACE,
         seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
         bump
     )]
     pub election_voter: Account<'info, ElectionVoter>,
 
+    // Reimburses the payer's rent when the election is sponsored
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn register_voter(ctx: Context<RegisterVoter>) -> Result<()> {
-    let election_voter = &mut ctx.accounts.election_voter;
+// Registration for NFT-gated elections: the voter must also hold an NFT
+// verified as part of the election's collection
+#[derive(Accounts)]
+pub struct RegisterNftVoter<'info> {
+    pub voter: Signer<'info>,
 
-    election_voter.voter = ctx.accounts.voter.key();
-    election_voter.election = ctx.accounts.election.key();
-    election_voter.registration_time = Clock::get()?.unix_timestamp;
-    election_voter.is_eligible = true;
-    election_voter.status = VoterStatus::Active;
-    election_voter.has_voted = false;
-    election_voter.bump = ctx.bumps.election_voter;
+    // Funds the new accounts; a relayer can pay so the voter needs no SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = election.nft_collection.is_some() @ VoterError::NotNftGated
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        seeds = [b"user_verification", election.organization.as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = user_verification.is_verified @ crate::error::VerificationError::VoterNotVerified
+    )]
+    pub user_verification: Account<'info, UserVerification>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ElectionVoter::SPACE,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = nft_token_account.owner == voter.key() @ VoterError::InvalidMembershipNft,
+        constraint = nft_token_account.mint == nft_mint.key() @ VoterError::InvalidMembershipNft,
+        constraint = nft_token_account.amount == 1 @ VoterError::InvalidMembershipNft,
+    )]
+    pub nft_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"metadata", Metadata::id().as_ref(), nft_mint.key().as_ref()],
+        seeds::program = Metadata::id(),
+        bump,
+        constraint = nft_metadata.collection.as_ref().is_some_and(|collection| {
+            collection.verified && Some(collection.key) == election.nft_collection
+        }) @ VoterError::InvalidMembershipNft,
+    )]
+    pub nft_metadata: Account<'info, MetadataAccount>,
+
+    // Required when the election allows one registration per NFT; init fails
+    // if the NFT has already been claimed
+    #[account(
+        init,
+        payer = payer,
+        space = NftClaim::SPACE,
+        seeds = [b"nft_claim", election.key().as_ref(), nft_mint.key().as_ref()],
+        bump
+    )]
+    pub nft_claim: Option<Account<'info, NftClaim>>,
+
+    // Reimburses the payer's rent when the election is sponsored
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Registration for allowlisted elections: a Merkle proof of the voter's
+// place on the roll stands in for a UserVerification account
+#[derive(Accounts)]
+pub struct RegisterAllowlistedVoter<'info> {
+    pub voter: Signer<'info>,
+
+    // Funds the new accounts; a relayer can pay so the voter needs no SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = election.voter_merkle_root.is_some() @ VoterError::NotAllowlisted
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ElectionVoter::SPACE,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    // Reimburses the payer's rent when the election is sponsored
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_voter_for_election(
+    ctx: Context<RegisterVoter>,
+    allowlist_proof: Vec<[u8; 32]>
+) -> Result<()> {
+    // Registration closes for good once voting starts
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.election.check_registration_open(now)?;
+
+    ctx.accounts.election.check_eligibility(
+        &(EligibilityEvidence {
+            voter: ctx.accounts.voter.key(),
+            user_verification: &ctx.accounts.user_verification,
+            allowlist_proof: &allowlist_proof,
+            accounts: ctx.remaining_accounts,
+            now,
+        })
+    )?;
+
+    let election_voter = &mut ctx.accounts.election_voter;
+    init_election_voter(
+        election_voter,
+        &ctx.accounts.election,
+        ctx.accounts.voter.key(),
+        ctx.accounts.payer.key(),
+        ctx.bumps.election_voter,
+        now
+    );
+    election_voter.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        election_voter,
+        &ctx.accounts.payer,
+        ElectionVoter::SPACE
+    )?;
 
     // Emit event for voter registration
     emit!(VoterRegistered {
         election: ctx.accounts.election.key(),
         voter: ctx.accounts.voter.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
     });
 
     Ok(())
 }
 
+pub fn register_nft_holder_for_election(ctx: Context<RegisterNftVoter>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.election.check_registration_open(now)?;
+
+    ctx.accounts.election.check_eligibility(
+        &(EligibilityEvidence {
+            voter: ctx.accounts.voter.key(),
+            user_verification: &ctx.accounts.user_verification,
+            allowlist_proof: &[],
+            accounts: ctx.remaining_accounts,
+            now,
+        })
+    )?;
+
+    let election = &ctx.accounts.election;
+    let nft_mint = ctx.accounts.nft_mint.key();
+
+    // Record the claim so the NFT can't register a second wallet
+    if election.one_vote_per_nft {
+        let nft_claim = ctx.accounts.nft_claim.as_mut().ok_or(VoterError::NftClaimRequired)?;
+        nft_claim.election = election.key();
+        nft_claim.mint = nft_mint;
+        nft_claim.voter = ctx.accounts.voter.key();
+        nft_claim.payer = ctx.accounts.payer.key();
+        nft_claim.sponsored_rent = 0;
+        nft_claim.bump = ctx.bumps.nft_claim.unwrap_or_default();
+    }
+
+    let election_voter = &mut ctx.accounts.election_voter;
+    init_election_voter(
+        election_voter,
+        election,
+        ctx.accounts.voter.key(),
+        ctx.accounts.payer.key(),
+        ctx.bumps.election_voter,
+        now
+    );
+    election_voter.nft_mint = Some(nft_mint);
+
+    // The NFT claim is rent the payer put up for this voter too
+    let payer = &ctx.accounts.payer;
+    let sponsorship = &mut ctx.accounts.sponsorship;
+    election_voter.sponsored_rent = reimburse_rent(
+        sponsorship.as_mut(),
+        election_voter,
+        payer,
+        ElectionVoter::SPACE
+    )?;
+    if election.one_vote_per_nft {
+        let nft_claim = ctx.accounts.nft_claim.as_mut().ok_or(VoterError::NftClaimRequired)?;
+        nft_claim.sponsored_rent = reimburse_rent(
+            sponsorship.as_mut(),
+            election_voter,
+            payer,
+            NftClaim::SPACE
+        )?;
+    }
+
+    emit!(VoterRegistered {
+        election: election.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub fn register_allowlisted_voter_for_election(
+    ctx: Context<RegisterAllowlistedVoter>,
+    proof: Vec<[u8; 32]>,
+    weight: u64
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let election = &ctx.accounts.election;
+    election.check_registration_open(now)?;
+
+    // The voter and their weight must be a leaf under the election's root
+    let root = election.voter_merkle_root.ok_or(VoterError::NotAllowlisted)?;
+    let leaf = allowlist_leaf(&ctx.accounts.voter.key(), weight);
+    require!(
+        weight > 0 && verify_merkle_proof(&proof, &root, leaf),
+        VoterError::InvalidAllowlistProof
+    );
+
+    let election_voter = &mut ctx.accounts.election_voter;
+    init_election_voter(
+        election_voter,
+        election,
+        ctx.accounts.voter.key(),
+        ctx.accounts.payer.key(),
+        ctx.bumps.election_voter,
+        now
+    );
+    election_voter.voting_power = weight;
+    election_voter.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        election_voter,
+        &ctx.accounts.payer,
+        ElectionVoter::SPACE
+    )?;
+
+    emit!(VoterRegistered {
+        election: election.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn init_election_voter(
+    election_voter: &mut ElectionVoter,
+    election: &Account<Election>,
+    voter: Pubkey,
+    payer: Pubkey,
+    bump: u8,
+    now: i64
+) {
+    election_voter.voter = voter;
+    election_voter.election = election.key();
+    election_voter.registration_time = now;
+    election_voter.is_eligible = true;
+    election_voter.status = VoterStatus::Active;
+    election_voter.has_voted = false;
+    election_voter.credits = election.credit_budget;
+    election_voter.deposited_amount = 0;
+    election_voter.nft_mint = None;
+    election_voter.voting_power = 1;
+    election_voter.delegate = None;
+    election_voter.delegator_count = 0;
+    election_voter.total_delegators = 0;
+    election_voter.voted_through = None;
+    election_voter.registered_by = None;
+    election_voter.sponsored_lamports = 0;
+    election_voter.payer = payer;
+    election_voter.sponsored_rent = 0;
+    election_voter.ballot_nonce = 0;
+    election_voter.status_history = vec![];
+    election_voter.bump = bump;
+}
+
 #[event]
 pub struct VoterRegistered {
     pub election: Pubkey,
@@ -0,0 +1,84 @@
+// instructions/reveal_vote.rs
+
+use anchor_lang::prelude::*;
+use crate::{ Ballot, D21Error, Election, ElectionVoter, VoterError, VoterStatus };
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.secret_ballot @ D21Error::NotSecretBallot
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = election_voter.is_eligible @ VoterError::VoterNotEligible,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"ballot", election.key().as_ref(), voter.key().as_ref()],
+        bump = ballot.bump,
+        constraint = !ballot.revealed @ D21Error::AlreadyRevealed,
+        constraint = !ballot.voided @ D21Error::BallotVoided
+    )]
+    pub ballot: Account<'info, Ballot>,
+}
+
+#[event]
+pub struct VoteRevealed {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn process_reveal(
+    ctx: Context<RevealVote>,
+    plus_votes: Vec<u8>,
+    minus_votes: Vec<u8>,
+    salt: [u8; 32]
+) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let ballot = &mut ctx.accounts.ballot;
+
+    let now = Clock::get()?.unix_timestamp;
+    election.check_reveal_window(now)?;
+    election.sync_status(now);
+
+    // The preimage must match what was committed during voting
+    let expected = Ballot::commitment_for(
+        &election.key(),
+        &ctx.accounts.voter.key(),
+        &plus_votes,
+        &minus_votes,
+        &salt
+    );
+    require!(ballot.commitment == Some(expected), D21Error::InvalidReveal);
+
+    // Validate ballot against the election's voting method
+    election.validate_ballot(&plus_votes, &minus_votes)?;
+
+    // Update vote counts
+    election.apply_ballot(&plus_votes, &minus_votes, ballot.weight)?;
+    election.revealed_ballots += 1;
+
+    ballot.plus_votes = plus_votes;
+    ballot.minus_votes = minus_votes;
+    ballot.revealed = true;
+
+    emit!(VoteRevealed {
+        election: election.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// instructions/review_verification.rs
+use anchor_lang::prelude::*;
+
+use crate::{ D21Error, Organization, UserVerification, VerificationError };
+
+#[derive(Accounts)]
+pub struct ReviewVerification<'info> {
+    #[account(mut)]
+    pub registrar: Signer<'info>,
+
+    #[account(
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump,
+        constraint = organization.is_registrar(&registrar.key()) @ VerificationError::NotRegistrar
+    )]
+    pub organization: Account<'info, Organization>,
+
+    /// CHECK: only receives the rent refund when a request is rejected
+    #[account(mut, address = user_verification.payer @ D21Error::InvalidRentRecipient)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"user_verification",
+            organization.key().as_ref(),
+            user_verification.user.as_ref(),
+        ],
+        bump = user_verification.bump,
+        constraint = !user_verification.is_verified @ VerificationError::AlreadyVerified
+    )]
+    pub user_verification: Account<'info, UserVerification>,
+}
+
+#[event]
+pub struct UserVerified {
+    pub user: Pubkey,
+    pub id_number: String,
+    pub user_type: u16,
+    pub registrar: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationRejected {
+    pub user: Pubkey,
+    pub registrar: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn approve_user_verification(ctx: Context<ReviewVerification>) -> Result<()> {
+    let verification = &mut ctx.accounts.user_verification;
+
+    verification.is_verified = true;
+    verification.verification_time = Clock::get()?.unix_timestamp;
+    verification.verified_by = Some(ctx.accounts.registrar.key());
+
+    // Emit verification event
+    emit!(UserVerified {
+        user: verification.user,
+        id_number: verification.id_number.clone(),
+        user_type: verification.user_type,
+        registrar: ctx.accounts.registrar.key(),
+        timestamp: verification.verification_time,
+    });
+
+    Ok(())
+}
+
+pub fn reject_user_verification(ctx: Context<ReviewVerification>) -> Result<()> {
+    // Close the request and refund whoever paid for it so the user can resubmit
+    let user = ctx.accounts.user_verification.user;
+    ctx.accounts.user_verification.close(ctx.accounts.payer.to_account_info())?;
+
+    emit!(VerificationRejected {
+        user,
+        registrar: ctx.accounts.registrar.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+// instructions/set_eligibility_rules.rs
+use anchor_lang::prelude::*;
+use crate::{
+    CategoryRegistry,
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionStatus,
+    EligibilityRule,
+    RuleLogic,
+};
+
+#[derive(Accounts)]
+pub struct SetEligibilityRules<'info> {
+    pub authority: Signer<'info>,
+
+    // Rules are fixed once registration opens so every voter faces the same checks.
+    // Allowlisted voters register with a proof alone, so rules would never be applied
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired,
+        constraint = election.voter_merkle_root.is_none() @ D21Error::AllowlistHasNoRules,
+        constraint = election.status == ElectionStatus::Created @ D21Error::EligibilityLocked
+    )]
+    pub election: Account<'info, Election>,
+
+    // UserType rules may only name the organisation's active categories
+    #[account(
+        seeds = [b"categories", election.organization.as_ref()],
+        bump = category_registry.bump
+    )]
+    pub category_registry: Account<'info, CategoryRegistry>,
+}
+
+pub fn update_eligibility_rules(
+    ctx: Context<SetEligibilityRules>,
+    logic: RuleLogic,
+    rules: Vec<EligibilityRule>
+) -> Result<()> {
+    Election::validate_eligibility_rules(&rules, &ctx.accounts.category_registry)?;
+
+    let election = &mut ctx.accounts.election;
+    election.eligibility_logic = logic.clone();
+    election.eligibility_rules = rules.clone();
+
+    emit!(EligibilityRulesUpdated {
+        election: election.key(),
+        logic,
+        rules,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct EligibilityRulesUpdated {
+    pub election: Pubkey,
+    pub logic: RuleLogic,
+    pub rules: Vec<EligibilityRule>,
+    pub timestamp: i64,
+}
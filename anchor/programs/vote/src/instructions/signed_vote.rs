@@ -0,0 +1,157 @@
+// instructions/signed_vote.rs
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+use crate::{
+    record_vote,
+    reimburse_rent,
+    verify_ed25519_instruction,
+    Ballot,
+    D21Error,
+    Election,
+    ElectionVoter,
+    Sponsorship,
+    UserVerification,
+    VerificationError,
+    VoterError,
+    VoterStatus,
+};
+
+// A ballot the voter signed off-chain, submitted by any relayer. The
+// transaction must carry an ed25519 program instruction verifying the voter's
+// signature over Ballot::signed_message immediately before this one.
+#[derive(Accounts)]
+pub struct SubmitSignedVote<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: not a signer; the ed25519 instruction proves the voter signed the ballot
+    pub voter: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    // Not needed by voters registered through an allowlist proof or put on the
+    // roll by the authority
+    #[account(
+        seeds = [b"user_verification", election.organization.as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = user_verification.is_verified @ VerificationError::VoterNotVerified,
+    )]
+    pub user_verification: Option<Account<'info, UserVerification>>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), voter.key().as_ref()],
+        bump = election_voter.bump,
+        constraint = election_voter.is_vouched_for(&election, user_verification.is_some()) @ VerificationError::VoterNotVerified,
+        constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
+        constraint = election_voter.is_eligible @ VoterError::VoterNotEligible,
+        constraint = !election_voter.has_voted @ D21Error::AlreadyVoted,
+        constraint = election_voter.delegate.is_none() @ VoterError::VoteDelegated,
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = Ballot::SPACE,
+        seeds = [b"ballot", election.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    // Reimburses the relayer's rent when the election is sponsored
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
+    /// CHECK: the instructions sysvar, used to read the ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SignedVoteSubmitted {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+pub fn process_signed_vote(
+    ctx: Context<SubmitSignedVote>,
+    plus_votes: Vec<u8>,
+    minus_votes: Vec<u8>,
+    nonce: u64,
+    expires_at: i64
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= expires_at, D21Error::SignedBallotExpired);
+    require!(nonce == ctx.accounts.election_voter.ballot_nonce, D21Error::InvalidBallotNonce);
+
+    // The signature must cover this election, voter, nonce, expiry and selection
+    let voter = ctx.accounts.voter.key();
+    let message = Ballot::signed_message(
+        &ctx.accounts.election.key(),
+        &voter,
+        nonce,
+        expires_at,
+        &plus_votes,
+        &minus_votes
+    );
+    let sysvar = ctx.accounts.instructions.to_account_info();
+    let current = load_current_index_checked(&sysvar)?;
+    require!(current > 0, D21Error::InvalidBallotSignature);
+    let ed25519_ix = load_instruction_at_checked((current - 1) as usize, &sysvar)?;
+    require!(
+        verify_ed25519_instruction(&ed25519_ix, &voter, &message),
+        D21Error::InvalidBallotSignature
+    );
+
+    // Each nonce is good for one submission
+    ctx.accounts.election_voter.ballot_nonce += 1;
+
+    ctx.accounts.ballot.payer = ctx.accounts.relayer.key();
+    ctx.accounts.ballot.bump = ctx.bumps.ballot;
+    record_vote(
+        &mut ctx.accounts.election,
+        &mut ctx.accounts.election_voter,
+        &mut ctx.accounts.ballot,
+        voter,
+        plus_votes,
+        minus_votes,
+        ctx.remaining_accounts
+    )?;
+
+    ctx.accounts.ballot.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        &mut ctx.accounts.election_voter,
+        &ctx.accounts.relayer,
+        Ballot::SPACE
+    )?;
+
+    emit!(SignedVoteSubmitted {
+        election: ctx.accounts.election.key(),
+        voter,
+        relayer: ctx.accounts.relayer.key(),
+        nonce,
+        timestamp: now,
+    });
+
+    Ok(())
+}
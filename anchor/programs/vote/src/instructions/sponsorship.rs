@@ -0,0 +1,180 @@
+// instructions/sponsorship.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{ transfer, Transfer };
+
+use crate::{ D21Error, Election, ElectionVoter, Sponsorship };
+
+#[derive(Accounts)]
+pub struct CreateSponsorship<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = authority.key() == election.authority @ D21Error::Unauthorized,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Sponsorship::SPACE,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can top the vault up, e.g. a student union backing turnout
+#[derive(Accounts)]
+pub struct FundSponsorship<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SponsorshipFunded {
+    pub election: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsoredRentReturned {
+    pub election: Pubkey,
+    pub account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentSponsored {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+pub fn create_election_sponsorship(
+    ctx: Context<CreateSponsorship>,
+    per_voter_cap: u64
+) -> Result<()> {
+    let sponsorship = &mut ctx.accounts.sponsorship;
+    sponsorship.election = ctx.accounts.election.key();
+    sponsorship.per_voter_cap = per_voter_cap;
+    sponsorship.total_reimbursed = 0;
+    sponsorship.bump = ctx.bumps.sponsorship;
+    Ok(())
+}
+
+pub fn fund_election_sponsorship(ctx: Context<FundSponsorship>, amount: u64) -> Result<()> {
+    require!(amount > 0, D21Error::InvalidSponsorshipAmount);
+
+    transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.sponsorship.to_account_info(),
+        }),
+        amount
+    )?;
+
+    emit!(SponsorshipFunded {
+        election: ctx.accounts.election.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Pays back the rent `payer` put up for one of the voter's accounts, limited
+// by the voter's remaining cap and what the vault holds above its own rent.
+// An empty or missing vault leaves the payer out of pocket rather than failing.
+// Returns the amount reimbursed, which the account records as sponsored_rent.
+pub(crate) fn reimburse_rent(
+    sponsorship: Option<&mut Account<Sponsorship>>,
+    election_voter: &mut ElectionVoter,
+    payer: &AccountInfo,
+    space: usize
+) -> Result<u64> {
+    let Some(sponsorship) = sponsorship else {
+        return Ok(0);
+    };
+
+    let rent = Rent::get()?;
+    let vault = sponsorship.to_account_info();
+    let available = vault.lamports().saturating_sub(rent.minimum_balance(vault.data_len()));
+    let amount = rent
+        .minimum_balance(space)
+        .min(sponsorship.per_voter_cap.saturating_sub(election_voter.sponsored_lamports))
+        .min(available);
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+    **payer.try_borrow_mut_lamports()? += amount;
+
+    election_voter.sponsored_lamports += amount;
+    sponsorship.total_reimbursed += amount;
+
+    emit!(RentSponsored {
+        election: sponsorship.election,
+        voter: election_voter.voter,
+        payer: payer.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(amount)
+}
+
+// Moves the sponsored part of a closing account's rent back into the vault
+// before the rest goes to its payer. Once the authority has closed the vault
+// there is nowhere to return it, so the payer keeps it all.
+pub(crate) fn return_sponsored_rent(
+    election: &Pubkey,
+    account: &AccountInfo,
+    vault: &AccountInfo,
+    sponsored_rent: u64
+) -> Result<()> {
+    if sponsored_rent == 0 || vault.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let amount = sponsored_rent.min(account.lamports());
+    **account.try_borrow_mut_lamports()? -= amount;
+    **vault.try_borrow_mut_lamports()? += amount;
+
+    emit!(SponsoredRentReturned {
+        election: *election,
+        account: account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+// instructions/tally.rs
+
+use anchor_lang::prelude::*;
+use crate::{ Ballot, D21Error, Election, ElectionFinalized, ElectionStatus, Tally, VOTE_SCALE };
+
+// Permissionless: once voting is closed anyone may open the tally and pay its rent
+#[derive(Accounts)]
+pub struct StartTally<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive,
+        constraint = election.voting_method.uses_tally() @ D21Error::NotTallyMethod
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Tally::space(election.candidates.len()),
+        seeds = [b"tally", election.key().as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless crank; ballot accounts are passed as remaining accounts
+#[derive(Accounts)]
+pub struct TallyBallots<'info> {
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"tally", election.key().as_ref()],
+        bump = tally.bump,
+        has_one = election
+    )]
+    pub tally: Account<'info, Tally>,
+}
+
+// Only whoever opened the tally or the election authority may discard a
+// round's progress, so nobody can stall a multi-transaction round
+#[derive(Accounts)]
+pub struct RestartTallyRound<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"tally", election.key().as_ref()],
+        bump = tally.bump,
+        has_one = election,
+        constraint = cranker.key() == tally.payer ||
+            cranker.key() == election.authority @ D21Error::Unauthorized
+    )]
+    pub tally: Account<'info, Tally>,
+}
+
+#[event]
+pub struct TallyRoundRestarted {
+    pub election: Pubkey,
+    pub round: u8,
+    pub ballots_discarded: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TallyRoundClosed {
+    pub election: Pubkey,
+    pub round: u8,
+    pub elected: Vec<u8>,
+    pub eliminated: Option<u8>,
+    pub timestamp: i64,
+}
+
+pub fn open_tally(ctx: Context<StartTally>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let tally = &mut ctx.accounts.tally;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Ballots must be closed, either by the authority or by the schedule
+    election.sync_status(now);
+    require!(
+        election.status == ElectionStatus::Tallying || election.results_due(now),
+        D21Error::ElectionStillRunning
+    );
+    election.status = ElectionStatus::Tallying;
+
+    tally.election = election.key();
+    tally.payer = ctx.accounts.payer.key();
+    tally.round = 0;
+    tally.cursor = Pubkey::default();
+    tally.ballots_counted = 0;
+    tally.exhausted = 0;
+    tally.counts = vec![0; election.candidates.len()];
+    tally.transfer_factors = vec![VOTE_SCALE; election.candidates.len()];
+    tally.removed = vec![];
+    tally.elected = vec![];
+    tally.eliminated = vec![];
+    tally.quota = 0;
+    tally.complete = false;
+    tally.bump = ctx.bumps.tally;
+
+    Ok(())
+}
+
+pub fn process_tally_ballots(ctx: Context<TallyBallots>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let tally = &mut ctx.accounts.tally;
+    let now = Clock::get()?.unix_timestamp;
+
+    for account in ctx.remaining_accounts.iter() {
+        // Strictly ascending addresses mean no ballot is counted twice in a round
+        require!(account.key() > tally.cursor, D21Error::BallotOutOfOrder);
+        require_keys_eq!(*account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let data = account.try_borrow_data()?;
+        let ballot = Ballot::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(ballot.election, election.key(), D21Error::InvalidTallyBallot);
+
+        // Voided ballots were already taken out of total_voters
+        if !ballot.voided {
+            tally.count_ballot(&ballot.plus_votes, ballot.weight);
+        }
+        tally.cursor = account.key();
+    }
+
+    // Close every round whose ballots have all been counted
+    let first_preferences = election.first_preferences();
+    let seats = election.num_winners as usize;
+    while tally.ballots_counted == election.total_voters && !tally.is_complete() {
+        let round = tally.round;
+        let (elected_before, eliminated_before) = (tally.elected.len(), tally.eliminated.len());
+        tally.close_round(&election.voting_method, seats, &first_preferences);
+
+        emit!(TallyRoundClosed {
+            election: election.key(),
+            round,
+            elected: tally.elected[elected_before..].to_vec(),
+            eliminated: tally.eliminated.get(eliminated_before).copied(),
+            timestamp: now,
+        });
+    }
+
+    if tally.is_complete() {
+        election.status = ElectionStatus::Ended;
+        election.end_time.get_or_insert(now);
+        election.apply_tally_results(tally);
+
+        emit!(ElectionFinalized {
+            election: election.key(),
+            winners: election.winners.clone(),
+            unrevealed_ballots: election.unrevealed_ballots(),
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+// A pass that skipped a ballot can never reach total_voters, and the cursor
+// has already moved past the missing address. The tally's payer or the
+// authority may discard the round's progress and feed its ballots again.
+pub fn reset_tally_round(ctx: Context<RestartTallyRound>) -> Result<()> {
+    let tally = &mut ctx.accounts.tally;
+    require!(tally.cursor != Pubkey::default(), D21Error::TallyRoundNotStarted);
+
+    let ballots_discarded = tally.ballots_counted;
+    tally.restart_round();
+
+    emit!(TallyRoundRestarted {
+        election: ctx.accounts.election.key(),
+        round: tally.round,
+        ballots_discarded,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
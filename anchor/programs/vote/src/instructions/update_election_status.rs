@@ -0,0 +1,72 @@
+// instructions/update_election_status.rs
+use anchor_lang::prelude::*;
+use crate::{ CommitteeError, D21Error, Election, ElectionStatus, OfficerPermission };
+
+#[derive(Accounts)]
+pub struct UpdateElectionStatus<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.can_act(&authority.key(), &OfficerPermission::UpdateElectionStatus) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired
+    )]
+    pub election: Account<'info, Election>,
+}
+
+pub fn change_election_status(
+    ctx: Context<UpdateElectionStatus>,
+    new_status: ElectionStatus
+) -> Result<()> {
+    apply_election_status(&mut ctx.accounts.election, new_status, Clock::get()?.unix_timestamp)
+}
+
+// Shared with committee proposals
+pub(crate) fn apply_election_status(
+    election: &mut Account<Election>,
+    new_status: ElectionStatus,
+    now: i64
+) -> Result<()> {
+    // Apply any scheduled transition before checking the requested one
+    election.sync_status(now);
+
+    if !election.status.can_transition_to(&new_status) {
+        return err!(D21Error::InvalidElectionTransition);
+    }
+
+    // Secret ballots must be revealed before tallying; public ones have nothing to reveal
+    if election.status == ElectionStatus::Voting {
+        require!(
+            election.secret_ballot == (new_status == ElectionStatus::Reveal),
+            D21Error::InvalidElectionTransition
+        );
+    }
+
+    // A scheduled start cannot be brought forward; otherwise record when voting opened
+    if new_status == ElectionStatus::Voting {
+        require!(election.start_time.is_none(), D21Error::ElectionNotStarted);
+        election.start_time = Some(now);
+    }
+
+    let old_status = election.status.clone();
+    election.status = new_status.clone();
+
+    emit!(ElectionStatusChanged {
+        election: election.key(),
+        old_status,
+        new_status,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ElectionStatusChanged {
+    pub election: Pubkey,
+    pub old_status: ElectionStatus,
+    pub new_status: ElectionStatus,
+    pub timestamp: i64,
+}
@@ -1,6 +1,17 @@
 // instructions/update_voter_status.rs
 use anchor_lang::prelude::*;
-use crate::{ D21Error, Election, ElectionVoter, VoterError, VoterStatus };
+use anchor_lang::system_program::{ transfer, Transfer };
+use crate::{
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionVoter,
+    OfficerPermission,
+    StatusChange,
+    StatusReason,
+    VoterError,
+    VoterStatus,
+};
 
 #[derive(Accounts)]
 pub struct UpdateVoterStatus<'info> {
@@ -9,7 +20,9 @@ pub struct UpdateVoterStatus<'info> {
 
     // The election this voter registration belongs to
     #[account(
-        constraint = authority.key() == election.authority @ D21Error::Unauthorized
+        constraint = election.can_act(&authority.key(), &OfficerPermission::UpdateVoterStatus) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired,
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
     )]
     pub election: Account<'info, Election>,
 
@@ -23,32 +36,116 @@ pub struct UpdateVoterStatus<'info> {
         bump = election_voter.bump,
     )]
     pub election_voter: Account<'info, ElectionVoter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn change_voter_status(
+    ctx: Context<UpdateVoterStatus>,
+    new_status: VoterStatus,
+    reason: StatusReason
+) -> Result<()> {
+    let recorder = StatusRecorder {
+        changed_by: ctx.accounts.authority.key(),
+        payer: &ctx.accounts.authority,
+        system_program: &ctx.accounts.system_program,
+        now: Clock::get()?.unix_timestamp,
+    };
+    apply_voter_status(
+        &ctx.accounts.election,
+        &mut ctx.accounts.election_voter,
+        new_status,
+        reason,
+        &recorder
+    )
 }
 
-pub fn update_voter_status(ctx: Context<UpdateVoterStatus>, new_status: VoterStatus) -> Result<()> {
-    let election_voter = &mut ctx.accounts.election_voter;
+// Who a status history entry is attributed to, and who pays for the room it
+// takes once the voter's account has to grow
+pub(crate) struct StatusRecorder<'a, 'info> {
+    pub changed_by: Pubkey, // Authority, officer, or executed committee proposal
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub now: i64,
+}
+
+// Shared with committee proposals, which record the proposal as `changed_by`
+pub(crate) fn apply_voter_status<'info>(
+    election: &Account<'info, Election>,
+    election_voter: &mut Account<'info, ElectionVoter>,
+    new_status: VoterStatus,
+    reason: StatusReason,
+    recorder: &StatusRecorder<'_, 'info>
+) -> Result<()> {
+    let now = recorder.now;
 
     // Check if transition is allowed
     if !election_voter.can_transition_to(&new_status) {
         return err!(VoterError::InvalidStatusTransition);
     }
 
-    // Record the status change
+    // The roll is frozen once voting opens, so pending voters can no longer be admitted
+    if election_voter.status == VoterStatus::Pending && new_status == VoterStatus::Active {
+        election.check_registration_open(now)?;
+    }
+
+    // Record the status change; only active voters may vote or delegate
     let old_status = election_voter.status.clone();
+    grow_status_history(
+        &election_voter.to_account_info(),
+        election_voter.status_history.len(),
+        recorder
+    )?;
+    election_voter.record(StatusChange {
+        from: old_status.clone(),
+        to: new_status.clone(),
+        reason: reason.clone(),
+        ballot_voided: false,
+        changed_by: recorder.changed_by,
+        timestamp: now,
+    });
     election_voter.status = new_status.clone();
+    election_voter.is_eligible = new_status == VoterStatus::Active;
 
     // Emit event for status change
     emit!(ElectionVoterStatusChanged {
-        election: ctx.accounts.election.key(),
+        election: election.key(),
         voter: election_voter.voter,
         old_status,
         new_status,
-        timestamp: Clock::get()?.unix_timestamp,
+        reason,
+        timestamp: now,
     });
 
     Ok(())
 }
 
+// Makes room for one more status history entry, reallocating the voter's
+// account at the recorder's expense once its current size is used up
+pub(crate) fn grow_status_history<'info>(
+    election_voter: &AccountInfo<'info>,
+    history_len: usize,
+    recorder: &StatusRecorder<'_, 'info>
+) -> Result<()> {
+    let space = ElectionVoter::space(history_len + 1);
+    if election_voter.data_len() >= space {
+        return Ok(());
+    }
+
+    let top_up = Rent::get()?.minimum_balance(space).saturating_sub(election_voter.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(recorder.system_program.clone(), Transfer {
+                from: recorder.payer.clone(),
+                to: election_voter.clone(),
+            }),
+            top_up
+        )?;
+    }
+    election_voter.realloc(space, false)?;
+    Ok(())
+}
+
 // Event definition
 #[event]
 pub struct ElectionVoterStatusChanged {
@@ -56,5 +153,6 @@ pub struct ElectionVoterStatusChanged {
     pub voter: Pubkey, 
     pub old_status: VoterStatus,
     pub new_status: VoterStatus,
+    pub reason: StatusReason,
     pub timestamp: i64,
 }
\ No newline at end of file
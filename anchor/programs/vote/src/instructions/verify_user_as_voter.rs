@@ -1,52 +1,89 @@
 use anchor_lang::prelude::*;
 
-use crate::{ UserType, UserVerification, VerificationError };
+use crate::{
+    constant::{ MAX_ID_NUMBER_LENGTH, MIN_ID_NUMBER_LENGTH },
+    CategoryRegistry,
+    Organization,
+    UserVerification,
+    VerificationError,
+};
 
 #[derive(Accounts)]
-pub struct VerifyUser<'info> {
-    #[account(mut)]
+pub struct RequestVerification<'info> {
     pub user: Signer<'info>,
 
+    // Funds the request; a relayer can pay so the user needs no SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Verifications are per organisation, so a user can hold one in each
+    #[account(
+        seeds = [b"organization", organization.id.as_bytes()],
+        bump = organization.bump
+    )]
+    pub organization: Account<'info, Organization>,
+
+    // The request stays pending until a registrar reviews it
     #[account(
         init,
-        payer = user,
+        payer = payer,
         space = UserVerification::SPACE,
-        seeds = [b"user_verification", user.key().as_ref()],
+        seeds = [b"user_verification", organization.key().as_ref(), user.key().as_ref()],
         bump
     )]
     pub user_verification: Account<'info, UserVerification>,
 
+    #[account(
+        seeds = [b"categories", organization.key().as_ref()],
+        bump = category_registry.bump
+    )]
+    pub category_registry: Account<'info, CategoryRegistry>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[event]
-pub struct UserVerified {
+pub struct VerificationRequested {
+    pub organization: Pubkey,
     pub user: Pubkey,
     pub id_number: String,
-    pub user_type: UserType,
+    pub user_type: u16,
     pub timestamp: i64,
 }
 
-pub fn verify_user(ctx: Context<VerifyUser>, id_number: String, user_type: UserType) -> Result<()> {
+pub fn request_user_verification(
+    ctx: Context<RequestVerification>,
+    id_number: String,
+    user_type: u16
+) -> Result<()> {
     // Validate ID number format (adjust regex based on your ID format)
-    if id_number.len() < 7 || id_number.len() > 12 {
+    if id_number.len() < MIN_ID_NUMBER_LENGTH || id_number.len() > MAX_ID_NUMBER_LENGTH {
         return err!(VerificationError::InvalidIdNumber);
     }
+    require!(
+        ctx.accounts.category_registry.is_active(user_type),
+        VerificationError::UnknownCategory
+    );
 
     let verification = &mut ctx.accounts.user_verification;
+    verification.organization = ctx.accounts.organization.key();
     verification.user = ctx.accounts.user.key();
+    verification.payer = ctx.accounts.payer.key();
     verification.id_number = id_number.clone();
-    verification.user_type = user_type.clone();
-    verification.is_verified = true;
-    verification.verification_time = Clock::get()?.unix_timestamp;
+    verification.user_type = user_type;
+    verification.is_verified = false;
+    verification.request_time = Clock::get()?.unix_timestamp;
+    verification.verification_time = 0;
+    verification.verified_by = None;
     verification.bump = ctx.bumps.user_verification;
 
-    // Emit verification event
-    emit!(UserVerified {
+    // Emit request event so registrars can pick it up
+    emit!(VerificationRequested {
+        organization: ctx.accounts.organization.key(),
         user: ctx.accounts.user.key(),
         id_number,
         user_type,
-        timestamp: verification.verification_time,
+        timestamp: verification.request_time,
     });
 
     Ok(())
@@ -0,0 +1,160 @@
+// instructions/void_ballot.rs
+use anchor_lang::prelude::*;
+use crate::{
+    grow_status_history,
+    load_delegation_tree,
+    Ballot,
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionStatus,
+    ElectionVoter,
+    OfficerPermission,
+    StatusChange,
+    StatusReason,
+    StatusRecorder,
+    VoterError,
+};
+
+// Voiding a delegate's ballot takes its whole delegation tree as remaining
+// accounts, so the void is recorded on everyone whose weight it carried
+#[derive(Accounts)]
+pub struct VoidBallot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.can_act(&authority.key(), &OfficerPermission::VoidBallot) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired
+    )]
+    pub election: Account<'info, Election>,
+
+    #[account(
+        mut,
+        seeds = [b"election_voter", election.key().as_ref(), election_voter.voter.as_ref()],
+        bump = election_voter.bump
+    )]
+    pub election_voter: Account<'info, ElectionVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"ballot", election.key().as_ref(), election_voter.voter.as_ref()],
+        bump = ballot.bump,
+        constraint = !ballot.voided @ D21Error::BallotVoided
+    )]
+    pub ballot: Account<'info, Ballot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct BallotVoided {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub reason: StatusReason,
+    pub voided_by: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn void_voter_ballot<'info>(
+    ctx: Context<'_, '_, '_, 'info, VoidBallot<'info>>,
+    reason: StatusReason
+) -> Result<()> {
+    let recorder = StatusRecorder {
+        changed_by: ctx.accounts.authority.key(),
+        payer: &ctx.accounts.authority,
+        system_program: &ctx.accounts.system_program,
+        now: Clock::get()?.unix_timestamp,
+    };
+    apply_void_ballot(
+        &mut ctx.accounts.election,
+        &mut ctx.accounts.election_voter,
+        &mut ctx.accounts.ballot,
+        reason,
+        ctx.remaining_accounts,
+        &recorder
+    )
+}
+
+// Shared with committee proposals. The ballot's counts are withdrawn along
+// with any weight delegated to it, and the voter and their delegators stay
+// marked as having voted, with the void in each of their histories.
+pub(crate) fn apply_void_ballot<'info>(
+    election: &mut Account<'info, Election>,
+    election_voter: &mut Account<'info, ElectionVoter>,
+    ballot: &mut Ballot,
+    reason: StatusReason,
+    delegators: &[AccountInfo<'info>],
+    recorder: &StatusRecorder<'_, 'info>
+) -> Result<()> {
+    let now = recorder.now;
+    let voided_by = recorder.changed_by;
+    require!(election_voter.can_void_ballot(), VoterError::BallotVoidNotAllowed);
+
+    // Tallies and final results assume the set of ballots no longer changes
+    election.sync_status(now);
+    require!(
+        matches!(election.status, ElectionStatus::Voting | ElectionStatus::Reveal),
+        D21Error::BallotsClosed
+    );
+
+    // Unrevealed commitments never reached the candidate counters
+    if ballot.revealed {
+        if ballot.vote_weights.is_empty() {
+            election.retract_ballot(&ballot.plus_votes, &ballot.minus_votes, ballot.weight)?;
+        } else {
+            election.retract_quadratic_ballot(&ballot.plus_votes, &ballot.vote_weights);
+        }
+        if election.secret_ballot {
+            election.revealed_ballots -= 1;
+        }
+    }
+    election.total_voters -= 1;
+    election.total_weight -= ballot.weight;
+    ballot.voided = true;
+
+    let void = |voter: &ElectionVoter| StatusChange {
+        from: voter.status.clone(),
+        to: voter.status.clone(),
+        reason: reason.clone(),
+        ballot_voided: true,
+        changed_by: voided_by,
+        timestamp: now,
+    };
+    grow_status_history(
+        &election_voter.to_account_info(),
+        election_voter.status_history.len(),
+        recorder
+    )?;
+    let change = void(election_voter);
+    election_voter.record(change);
+
+    // Delegators left out of the ballot's weight when it was cast are skipped
+    if election_voter.delegator_count > 0 {
+        let tree = load_delegation_tree(&election.key(), election_voter, delegators)?;
+        for (info, mut delegator) in delegators.iter().zip(tree) {
+            if delegator.voted_through != Some(election_voter.voter) {
+                continue;
+            }
+            grow_status_history(info, delegator.status_history.len(), recorder)?;
+            let change = void(&delegator);
+            delegator.record(change);
+            delegator.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    emit!(BallotVoided {
+        election: election.key(),
+        voter: election_voter.voter,
+        weight: ballot.weight,
+        reason,
+        voided_by,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -2,11 +2,13 @@
 
 use anchor_lang::prelude::*;
 use crate::{
+    apply_delegations,
+    reimburse_rent,
     Ballot,
     D21Error,
     Election,
-    ElectionStatus,
     ElectionVoter,
+    Sponsorship,
     UserVerification,
     VerificationError,
     VoterError,
@@ -15,24 +17,28 @@ use crate::{
 
 #[derive(Accounts)]
 pub struct CastVote<'info> {
-    #[account(mut)]
     pub voter: Signer<'info>,
 
+    // Funds the new accounts; a relayer can pay so the voter needs no SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
         mut,
-        seeds = [b"election", election.authority.key().as_ref(), election.id.as_bytes()],
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
         bump = election.bump,
-        constraint = election.status == ElectionStatus::Active @ D21Error::ElectionNotActive
+        constraint = !election.status.is_terminal() @ D21Error::ElectionNotActive
     )]
     pub election: Account<'info, Election>,
 
-    // Add verification check
+    // Add verification check; not needed by voters registered through an allowlist
+    // proof or put on the roll by the authority
     #[account(
-        seeds = [b"user_verification", voter.key().as_ref()],
+        seeds = [b"user_verification", election.organization.as_ref(), voter.key().as_ref()],
         bump,
         constraint = user_verification.is_verified @ VerificationError::VoterNotVerified,
     )]
-    pub user_verification: Account<'info, UserVerification>,
+    pub user_verification: Option<Account<'info, UserVerification>>,
 
     #[account(
         mut,
@@ -40,21 +46,31 @@ pub struct CastVote<'info> {
         bump = election_voter.bump,
         constraint = election_voter.voter == voter.key(),
         constraint = election_voter.election == election.key(),
+        constraint = election_voter.is_vouched_for(&election, user_verification.is_some()) @ VerificationError::VoterNotVerified,
         constraint = election_voter.status == VoterStatus::Active @ VoterError::VoterNotEligible,
         constraint = election_voter.is_eligible @VoterError::VoterNotEligible,
         constraint = !election_voter.has_voted @ D21Error::AlreadyVoted,
+        constraint = election_voter.delegate.is_none() @ VoterError::VoteDelegated,
     )]
     pub election_voter: Account<'info, ElectionVoter>,
 
     #[account(
         init,
-        payer = voter,
+        payer = payer,
         space = Ballot::SPACE,
         seeds = [b"ballot", election.key().as_ref(), voter.key().as_ref()],
         bump
     )]
     pub ballot: Account<'info, Ballot>,
 
+    // Reimburses the payer's rent when the election is sponsored
+    #[account(
+        mut,
+        seeds = [b"sponsorship", election.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -63,54 +79,172 @@ pub fn process_vote(
     plus_votes: Vec<u8>,
     minus_votes: Vec<u8>
 ) -> Result<()> {
-    let election = &mut ctx.accounts.election;
-    let ballot = &mut ctx.accounts.ballot;
-    let election_voter = &mut ctx.accounts.election_voter;
+    ctx.accounts.ballot.payer = ctx.accounts.payer.key();
+    ctx.accounts.ballot.bump = ctx.bumps.ballot;
+    record_vote(
+        &mut ctx.accounts.election,
+        &mut ctx.accounts.election_voter,
+        &mut ctx.accounts.ballot,
+        ctx.accounts.voter.key(),
+        plus_votes,
+        minus_votes,
+        ctx.remaining_accounts
+    )?;
+
+    ctx.accounts.ballot.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        &mut ctx.accounts.election_voter,
+        &ctx.accounts.payer,
+        Ballot::SPACE
+    )?;
+    Ok(())
+}
+
+// Shared with signed ballots submitted by a relayer
+pub(crate) fn record_vote(
+    election: &mut Account<Election>,
+    election_voter: &mut Account<ElectionVoter>,
+    ballot: &mut Account<Ballot>,
+    voter: Pubkey,
+    plus_votes: Vec<u8>,
+    minus_votes: Vec<u8>,
+    delegators: &[AccountInfo]
+) -> Result<()> {
+    // Votes are only accepted during the voting phase
+    let now = Clock::get()?.unix_timestamp;
+    election.check_voting_window(now)?;
+    election.sync_status(now);
+
+    // Secret ballots go through commit_vote instead
+    require!(!election.secret_ballot, D21Error::SecretBallotRequired);
 
-    // Validate vote counts
-    require!(plus_votes.len() <= (election.num_plus_votes as usize), D21Error::TooManyPlusVotes);
-    require!(minus_votes.len() <= (election.num_minus_votes as usize), D21Error::TooManyMinusVotes);
-    require!(plus_votes.len() >= 2 || minus_votes.is_empty(), D21Error::InsufficientPlusVotes);
-
-    // Validate candidate indices
-    let candidate_count = election.candidates.len();
-    for &idx in plus_votes.iter().chain(minus_votes.iter()) {
-        require!((idx as usize) < candidate_count, D21Error::InvalidCandidate);
-    }
-
-    // Check for overlapping votes
-    for &plus_idx in plus_votes.iter() {
-        require!(!minus_votes.contains(&plus_idx), D21Error::OverlappingVotes);
-    }
-
-    // Check for duplicate votes
-    let mut all_votes = plus_votes.clone();
-    all_votes.extend(minus_votes.iter());
-    let mut vote_set = all_votes.clone();
-    vote_set.sort();
-    vote_set.dedup();
-    require!(vote_set.len() == all_votes.len(), D21Error::DuplicateVotes);
+    // Validate ballot against the election's voting method
+    election.validate_ballot(&plus_votes, &minus_votes)?;
+
+    // Delegated weight rides on this ballot; the delegators come in as remaining accounts
+    let weight = election
+        .voting_weight(election_voter)?
+        .checked_add(apply_delegations(election, election_voter, delegators, now)?)
+        .ok_or(D21Error::VoteCountOverflow)?;
 
     // Store ballot
-    ballot.voter = ctx.accounts.voter.key();
+    ballot.voter = voter;
     ballot.election = election.key();
     ballot.plus_votes = plus_votes.clone();
     ballot.minus_votes = minus_votes.clone();
-    ballot.bump = ctx.bumps.ballot;
+    ballot.vote_weights = vec![];
+    ballot.weight = weight;
+    ballot.commitment = None;
+    ballot.revealed = true;
+    ballot.voided = false;
 
     // Update vote counts
-    for idx in plus_votes {
-        election.candidates[idx as usize].plus_votes += 1;
-    }
-    for idx in minus_votes {
-        election.candidates[idx as usize].minus_votes += 1;
-    }
+    election.apply_ballot(&plus_votes, &minus_votes, weight)?;
 
     // Mark voter as having voted
     election_voter.has_voted = true;
 
     // Increment total voters
     election.total_voters += 1;
+    election.add_weight(weight)?;
+
+    Ok(())
+}
+
+pub fn process_commit(ctx: Context<CastVote>, commitment: [u8; 32]) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let ballot = &mut ctx.accounts.ballot;
+    let election_voter = &mut ctx.accounts.election_voter;
+
+    // Commitments are only accepted during the voting phase
+    let now = Clock::get()?.unix_timestamp;
+    election.check_voting_window(now)?;
+    election.sync_status(now);
+
+    require!(election.secret_ballot, D21Error::NotSecretBallot);
+
+    // The weight, including any delegated to this voter, is fixed now and
+    // applied when the ballot is revealed
+    let weight = election
+        .voting_weight(election_voter)?
+        .checked_add(apply_delegations(election, election_voter, ctx.remaining_accounts, now)?)
+        .ok_or(D21Error::VoteCountOverflow)?;
+
+    // Store the commitment; the selection stays hidden until reveal_vote
+    ballot.voter = ctx.accounts.voter.key();
+    ballot.election = election.key();
+    ballot.plus_votes = vec![];
+    ballot.minus_votes = vec![];
+    ballot.vote_weights = vec![];
+    ballot.weight = weight;
+    ballot.commitment = Some(commitment);
+    ballot.revealed = false;
+    ballot.voided = false;
+    ballot.payer = ctx.accounts.payer.key();
+    ballot.bump = ctx.bumps.ballot;
+    ballot.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        election_voter,
+        &ctx.accounts.payer,
+        Ballot::SPACE
+    )?;
+
+    // Mark voter as having voted
+    election_voter.has_voted = true;
+
+    // Increment total voters
+    election.total_voters += 1;
+    election.add_weight(weight)?;
+
+    Ok(())
+}
+
+pub fn process_quadratic_vote(
+    ctx: Context<CastVote>,
+    plus_votes: Vec<u8>,
+    vote_weights: Vec<u16>
+) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let ballot = &mut ctx.accounts.ballot;
+    let election_voter = &mut ctx.accounts.election_voter;
+
+    // Votes are only accepted during the voting phase
+    let now = Clock::get()?.unix_timestamp;
+    election.check_voting_window(now)?;
+    election.sync_status(now);
+
+    // The ballot's cost must fit within the voter's remaining credits
+    let cost = election.validate_quadratic_ballot(&plus_votes, &vote_weights, election_voter.credits)?;
+
+    // Store ballot
+    ballot.voter = ctx.accounts.voter.key();
+    ballot.election = election.key();
+    ballot.plus_votes = plus_votes.clone();
+    ballot.minus_votes = vec![];
+    ballot.vote_weights = vote_weights.clone();
+    ballot.weight = 1;
+    ballot.commitment = None;
+    ballot.revealed = true;
+    ballot.voided = false;
+    ballot.payer = ctx.accounts.payer.key();
+    ballot.bump = ctx.bumps.ballot;
+    ballot.sponsored_rent = reimburse_rent(
+        ctx.accounts.sponsorship.as_mut(),
+        election_voter,
+        &ctx.accounts.payer,
+        Ballot::SPACE
+    )?;
+
+    // Update weighted totals
+    election.apply_quadratic_ballot(&plus_votes, &vote_weights);
+
+    // Spend the credits and mark voter as having voted
+    election_voter.credits -= cost;
+    election_voter.has_voted = true;
+
+    // Increment total voters
+    election.total_voters += 1;
+    election.total_weight += 1;
 
     Ok(())
 }
@@ -0,0 +1,152 @@
+// instructions/voter_roll.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate,
+    assign,
+    create_account,
+    transfer,
+    Allocate,
+    Assign,
+    CreateAccount,
+    Transfer,
+};
+
+use crate::{
+    constant::MAX_ROLL_BATCH,
+    init_election_voter,
+    CommitteeError,
+    D21Error,
+    Election,
+    ElectionVoter,
+    OfficerPermission,
+    VoterError,
+    VoterRegistered,
+    VoterStatus,
+};
+
+// The ElectionVoter PDAs for `voters` are passed as writable remaining
+// accounts, in the same order; the authority pays their rent
+#[derive(Accounts)]
+pub struct AddToVoterRoll<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"election", election.organization.as_ref(), election.id.as_bytes()],
+        bump = election.bump,
+        constraint = election.can_act(&authority.key(), &OfficerPermission::ManageVoterRoll) @ D21Error::Unauthorized,
+        constraint = election.committee.is_empty() @ CommitteeError::CommitteeApprovalRequired
+    )]
+    pub election: Account<'info, Election>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Voters join the roll as Pending and vote once update_voter_status activates
+// them; anyone already registered is left untouched so rolls can be re-imported
+pub fn add_voters_to_roll<'info>(
+    ctx: Context<'_, '_, '_, 'info, AddToVoterRoll<'info>>,
+    voters: Vec<Pubkey>
+) -> Result<()> {
+    require!(
+        !voters.is_empty() && voters.len() <= MAX_ROLL_BATCH,
+        VoterError::TooManyRollEntries
+    );
+    require!(ctx.remaining_accounts.len() == voters.len(), VoterError::InvalidRollEntry);
+
+    let election = &ctx.accounts.election;
+    let now = Clock::get()?.unix_timestamp;
+    election.check_registration_open(now)?;
+
+    let election_key = election.key();
+    let authority = ctx.accounts.authority.key();
+    let rent = Rent::get()?.minimum_balance(ElectionVoter::SPACE);
+
+    for (voter, info) in voters.iter().zip(ctx.remaining_accounts) {
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"election_voter", election_key.as_ref(), voter.as_ref()],
+            &crate::ID
+        );
+        require!(info.key() == address && info.is_writable, VoterError::InvalidRollEntry);
+
+        if info.owner == &crate::ID {
+            continue;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[
+            &[b"election_voter", election_key.as_ref(), voter.as_ref(), &[bump]],
+        ];
+        create_pda(
+            &ctx.accounts.system_program,
+            &ctx.accounts.authority,
+            info,
+            rent,
+            signer_seeds
+        )?;
+
+        let mut election_voter = ElectionVoter::default();
+        init_election_voter(&mut election_voter, election, *voter, authority, bump, now);
+        election_voter.status = VoterStatus::Pending;
+        election_voter.is_eligible = false;
+        election_voter.registered_by = Some(authority);
+        election_voter.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(VoterRegistered {
+            election: election_key,
+            voter: *voter,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+// Same steps as Anchor's `init`, including for an address someone has
+// already sent lamports to
+fn create_pda<'info>(
+    system_program: &Program<'info, System>,
+    payer: &Signer<'info>,
+    info: &AccountInfo<'info>,
+    rent: u64,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()> {
+    let system_program = system_program.to_account_info();
+    let payer = payer.to_account_info();
+
+    if info.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount { from: payer, to: info.clone() },
+                signer_seeds
+            ),
+            rent,
+            ElectionVoter::SPACE as u64,
+            &crate::ID
+        );
+    }
+
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(system_program.clone(), Transfer { from: payer, to: info.clone() }),
+            top_up
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: info.clone() },
+            signer_seeds
+        ),
+        ElectionVoter::SPACE as u64
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program,
+            Assign { account_to_assign: info.clone() },
+            signer_seeds
+        ),
+        &crate::ID
+    )
+}
@@ -5,11 +5,15 @@ mod instructions;
 mod state;
 mod error;
 mod constant;
+mod merkle;
+mod ed25519;
 
 pub use instructions::*;
 pub use error::*;
 pub use state::*;
 pub use constant::*;
+pub use merkle::*;
+pub use ed25519::*;
 
 declare_id!("CbwSkuSw474aJCRBaJE3wvpwnkRRkCQbZc1NMrmrTXMS");
 
@@ -22,47 +26,288 @@ pub mod vote {
         election_id: String,
         election_name: String,
         candidates: Vec<Pubkey>,
-        num_winners: u8,
-        num_plus_votes: u8,
-        num_minus_votes: u8,
-        allowed_voter_types: Vec<UserType>
-    ) -> Result<()> {
-        instructions::initialize_election(
-            ctx,
-            election_id,
-            election_name,
-            candidates,
-            num_winners,
-            num_plus_votes,
-            num_minus_votes,
-            allowed_voter_types
-        )
+        config: ElectionConfig
+    ) -> Result<()> {
+        instructions::initialize_election(ctx, election_id, election_name, candidates, config)
     }
 
     pub fn vote(ctx: Context<CastVote>, plus_votes: Vec<u8>, minus_votes: Vec<u8>) -> Result<()> {
         instructions::process_vote(ctx, plus_votes, minus_votes)
     }
 
+    pub fn quadratic_vote(
+        ctx: Context<CastVote>,
+        plus_votes: Vec<u8>,
+        vote_weights: Vec<u16>
+    ) -> Result<()> {
+        instructions::process_quadratic_vote(ctx, plus_votes, vote_weights)
+    }
+
+    pub fn submit_signed_vote(
+        ctx: Context<SubmitSignedVote>,
+        plus_votes: Vec<u8>,
+        minus_votes: Vec<u8>,
+        nonce: u64,
+        expires_at: i64
+    ) -> Result<()> {
+        instructions::process_signed_vote(ctx, plus_votes, minus_votes, nonce, expires_at)
+    }
+
+    pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
+        instructions::delegate_election_vote(ctx)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        instructions::revoke_election_delegation(ctx)
+    }
+
+    pub fn create_sponsorship(ctx: Context<CreateSponsorship>, per_voter_cap: u64) -> Result<()> {
+        instructions::create_election_sponsorship(ctx, per_voter_cap)
+    }
+
+    pub fn fund_sponsorship(ctx: Context<FundSponsorship>, amount: u64) -> Result<()> {
+        instructions::fund_election_sponsorship(ctx, amount)
+    }
+
+    pub fn commit_vote(ctx: Context<CastVote>, commitment: [u8; 32]) -> Result<()> {
+        instructions::process_commit(ctx, commitment)
+    }
+
+    pub fn change_commitment(ctx: Context<ChangeVote>, commitment: [u8; 32]) -> Result<()> {
+        instructions::process_commitment_change(ctx, commitment)
+    }
+
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        plus_votes: Vec<u8>,
+        minus_votes: Vec<u8>,
+        salt: [u8; 32]
+    ) -> Result<()> {
+        instructions::process_reveal(ctx, plus_votes, minus_votes, salt)
+    }
+
+    pub fn change_vote(
+        ctx: Context<ChangeVote>,
+        plus_votes: Vec<u8>,
+        minus_votes: Vec<u8>
+    ) -> Result<()> {
+        instructions::process_vote_change(ctx, plus_votes, minus_votes)
+    }
+
+    pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
+        instructions::deposit_voting_tokens(ctx, amount)
+    }
+
+    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
+        instructions::withdraw_voting_tokens(ctx)
+    }
+
     pub fn end(ctx: Context<EndElection>) -> Result<()> {
         instructions::end_election(ctx)
     }
 
-    pub fn register_voter(ctx: Context<RegisterVoter>) -> Result<()> {
-        instructions::register_voter(ctx)
+    pub fn finalize(ctx: Context<FinalizeElection>) -> Result<()> {
+        instructions::finalize_election(ctx)
+    }
+
+    pub fn start_tally(ctx: Context<StartTally>) -> Result<()> {
+        instructions::open_tally(ctx)
+    }
+
+    pub fn tally_ballots(ctx: Context<TallyBallots>) -> Result<()> {
+        instructions::process_tally_ballots(ctx)
+    }
+
+    pub fn restart_tally_round(ctx: Context<RestartTallyRound>) -> Result<()> {
+        instructions::reset_tally_round(ctx)
+    }
+
+    pub fn cancel(ctx: Context<CancelElection>, reason: String) -> Result<()> {
+        instructions::cancel_election(ctx, reason)
+    }
+
+    pub fn close_election(ctx: Context<CloseElection>) -> Result<()> {
+        instructions::close_election_account(ctx)
+    }
+
+    pub fn close_election_voter(ctx: Context<CloseElectionVoter>) -> Result<()> {
+        instructions::close_election_voter_account(ctx)
+    }
+
+    pub fn close_ballot(ctx: Context<CloseBallot>) -> Result<()> {
+        instructions::close_ballot_account(ctx)
+    }
+
+    pub fn close_tally(ctx: Context<CloseTally>) -> Result<()> {
+        instructions::close_tally_account(ctx)
+    }
+
+    pub fn close_nft_claim(ctx: Context<CloseNftClaim>) -> Result<()> {
+        instructions::close_nft_claim_account(ctx)
+    }
+
+    pub fn close_sponsorship(ctx: Context<CloseSponsorship>) -> Result<()> {
+        instructions::close_sponsorship_account(ctx)
+    }
+
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        instructions::close_proposal_account(ctx)
+    }
+
+    pub fn update_election_status(
+        ctx: Context<UpdateElectionStatus>,
+        new_status: ElectionStatus
+    ) -> Result<()> {
+        instructions::change_election_status(ctx, new_status)
+    }
+
+    pub fn set_eligibility_rules(
+        ctx: Context<SetEligibilityRules>,
+        logic: RuleLogic,
+        rules: Vec<EligibilityRule>
+    ) -> Result<()> {
+        instructions::update_eligibility_rules(ctx, logic, rules)
+    }
+
+    pub fn transfer_authority(
+        ctx: Context<ManageElectionAuthority>,
+        new_authority: Option<Pubkey>
+    ) -> Result<()> {
+        instructions::propose_authority_transfer(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority_transfer(ctx)
+    }
+
+    pub fn set_officer(
+        ctx: Context<ManageElectionAuthority>,
+        officer: Pubkey,
+        permissions: Vec<OfficerPermission>
+    ) -> Result<()> {
+        instructions::set_election_officer(ctx, officer, permissions)
+    }
+
+    pub fn set_committee(
+        ctx: Context<SetCommittee>,
+        committee: Vec<Pubkey>,
+        approval_threshold: u8
+    ) -> Result<()> {
+        instructions::set_election_committee(ctx, committee, approval_threshold)
+    }
+
+    pub fn propose_action(
+        ctx: Context<ProposeAction>,
+        action: AdminAction,
+        expires_at: i64
+    ) -> Result<()> {
+        instructions::propose_admin_action(ctx, action, expires_at)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::approve_admin_proposal(ctx)
+    }
+
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>
+    ) -> Result<()> {
+        instructions::execute_admin_proposal(ctx)
+    }
+
+    pub fn register_voter(
+        ctx: Context<RegisterVoter>,
+        allowlist_proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        instructions::register_voter_for_election(ctx, allowlist_proof)
+    }
+
+    pub fn register_nft_voter(ctx: Context<RegisterNftVoter>) -> Result<()> {
+        instructions::register_nft_holder_for_election(ctx)
+    }
+
+    pub fn register_allowlisted_voter(
+        ctx: Context<RegisterAllowlistedVoter>,
+        proof: Vec<[u8; 32]>,
+        weight: u64
+    ) -> Result<()> {
+        instructions::register_allowlisted_voter_for_election(ctx, proof, weight)
+    }
+
+    pub fn add_to_voter_roll<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddToVoterRoll<'info>>,
+        voters: Vec<Pubkey>
+    ) -> Result<()> {
+        instructions::add_voters_to_roll(ctx, voters)
     }
 
     pub fn update_voter_status(
         ctx: Context<UpdateVoterStatus>,
-        new_status: VoterStatus
+        new_status: VoterStatus,
+        reason: StatusReason
+    ) -> Result<()> {
+        instructions::change_voter_status(ctx, new_status, reason)
+    }
+
+    pub fn void_ballot<'info>(
+        ctx: Context<'_, '_, '_, 'info, VoidBallot<'info>>,
+        reason: StatusReason
     ) -> Result<()> {
-        instructions::update_voter_status(ctx, new_status)
+        instructions::void_voter_ballot(ctx, reason)
     }
 
-    pub fn verify_user(
-        ctx: Context<VerifyUser>,
+    pub fn request_verification(
+        ctx: Context<RequestVerification>,
         id_number: String,
-        user_type: UserType
+        user_type: u16
+    ) -> Result<()> {
+        instructions::request_user_verification(ctx, id_number, user_type)
+    }
+
+    pub fn approve_verification(ctx: Context<ReviewVerification>) -> Result<()> {
+        instructions::approve_user_verification(ctx)
+    }
+
+    pub fn reject_verification(ctx: Context<ReviewVerification>) -> Result<()> {
+        instructions::reject_user_verification(ctx)
+    }
+
+    pub fn create_organization(
+        ctx: Context<CreateOrganization>,
+        org_id: String,
+        name: String,
+        admin: Pubkey
     ) -> Result<()> {
-        instructions::verify_user(ctx, id_number, user_type)
+        instructions::create_organization_account(ctx, org_id, name, admin)
+    }
+
+    pub fn grant_role(
+        ctx: Context<ManageOrganization>,
+        role: OrgRole,
+        member: Pubkey
+    ) -> Result<()> {
+        instructions::grant_organization_role(ctx, role, member)
+    }
+
+    pub fn revoke_role(
+        ctx: Context<ManageOrganization>,
+        role: OrgRole,
+        member: Pubkey
+    ) -> Result<()> {
+        instructions::revoke_organization_role(ctx, role, member)
+    }
+
+    pub fn initialize_categories(
+        ctx: Context<InitializeCategories>,
+        names: Vec<String>
+    ) -> Result<()> {
+        instructions::initialize_category_registry(ctx, names)
+    }
+
+    pub fn add_category(ctx: Context<ManageCategories>, name: String) -> Result<()> {
+        instructions::add_voter_category(ctx, name)
+    }
+
+    pub fn retire_category(ctx: Context<ManageCategories>, id: u16) -> Result<()> {
+        instructions::retire_voter_category(ctx, id)
     }
 }
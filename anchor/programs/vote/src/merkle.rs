@@ -0,0 +1,22 @@
+// merkle.rs
+
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::prelude::Pubkey;
+
+// Leaves and internal nodes are domain-separated so a node can't pass as a
+// leaf; pairs are hashed in sorted order so proofs need no position bits
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+// sha256(0x00 || voter || weight as u64 little-endian)
+pub fn allowlist_leaf(voter: &Pubkey, weight: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, voter.as_ref(), &weight.to_le_bytes()]).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[NODE_PREFIX, &first, &second]).to_bytes()
+    });
+    computed == *root
+}
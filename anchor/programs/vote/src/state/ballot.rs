@@ -1,6 +1,7 @@
 // state/ballot.rs
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 #[account]
 #[derive(Default)]
@@ -9,6 +10,19 @@ pub struct Ballot {
     pub election: Pubkey,
     pub plus_votes: Vec<u8>,
     pub minus_votes: Vec<u8>,
+    // Quadratic ballots only: votes given to each plus_votes candidate
+    pub vote_weights: Vec<u16>,
+    // Voting power behind this ballot: 1, or the voter's escrowed tokens when cast
+    pub weight: u64,
+    // Secret ballots store only a commitment until the reveal phase
+    pub commitment: Option<[u8; 32]>,
+    pub revealed: bool,
+    // Set when the authority voids the ballot; its counts have been withdrawn
+    pub voided: bool,
+    // Funded this account and gets its rent back on close
+    pub payer: Pubkey,
+    // Part of this account's rent the sponsorship covered; returned to it on close
+    pub sponsored_rent: u64,
     pub bump: u8,
 }
 
@@ -18,8 +32,63 @@ impl Ballot {
         32 + // voter
         32 + // election
         4 +
-        1 * 10 + // plus_votes vec (max 10)
+        10 + // plus_votes vec (max 10)
         4 +
-        1 * 5 + // minus_votes vec (max 5)
+        5 + // minus_votes vec (max 5)
+        4 +
+        2 * 10 + // vote_weights vec (max 10)
+        8 + // weight
+        33 + // commitment option
+        1 + // revealed
+        1 + // voided
+        32 + // payer
+        8 + // sponsored_rent
         1; // bump
+
+    // sha256(election || voter || len(plus) || plus || len(minus) || minus || salt)
+    pub fn commitment_for(
+        election: &Pubkey,
+        voter: &Pubkey,
+        plus_votes: &[u8],
+        minus_votes: &[u8],
+        salt: &[u8; 32]
+    ) -> [u8; 32] {
+        hashv(
+            &[
+                election.as_ref(),
+                voter.as_ref(),
+                &[plus_votes.len() as u8],
+                plus_votes,
+                &[minus_votes.len() as u8],
+                minus_votes,
+                salt,
+            ]
+        ).to_bytes()
+    }
+
+    // What a voter signs off-chain for a relayer to submit:
+    // prefix || election || voter || nonce || expires_at || len(plus) || plus || len(minus) || minus
+    pub fn signed_message(
+        election: &Pubkey,
+        voter: &Pubkey,
+        nonce: u64,
+        expires_at: i64,
+        plus_votes: &[u8],
+        minus_votes: &[u8]
+    ) -> Vec<u8> {
+        [
+            SIGNED_BALLOT_PREFIX,
+            election.as_ref(),
+            voter.as_ref(),
+            &nonce.to_le_bytes(),
+            &expires_at.to_le_bytes(),
+            &[plus_votes.len() as u8],
+            plus_votes,
+            &[minus_votes.len() as u8],
+            minus_votes,
+        ].concat()
+    }
 }
+
+// Keeps a signed ballot from passing as any other signed message
+const SIGNED_BALLOT_PREFIX: &[u8] = b"d21-ballot-v1";
@@ -0,0 +1,41 @@
+// state/category_registry.rs
+use anchor_lang::prelude::*;
+
+use crate::constant::{ MAX_CATEGORIES, MAX_CATEGORY_NAME_LENGTH };
+
+// A voter category defined by the organisation (e.g. "Student", "Alumni").
+// Ids are never reused, so a retired category keeps its meaning in old records
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default)]
+pub struct VoterCategory {
+    pub id: u16,
+    pub name: String,
+    pub active: bool,
+}
+
+#[account]
+#[derive(Default)]
+pub struct CategoryRegistry {
+    // Categories in id order; retired ones stay in place
+    pub categories: Vec<VoterCategory>,
+    pub bump: u8,
+}
+
+impl CategoryRegistry {
+    pub const SPACE: usize =
+        8 + // discriminator
+        4 +
+        MAX_CATEGORIES *
+            (2 + // id
+                4 +
+                MAX_CATEGORY_NAME_LENGTH + // name
+                1) + // active
+        1; // bump
+
+    pub fn is_active(&self, id: u16) -> bool {
+        self.categories.get(id as usize).is_some_and(|category| category.active)
+    }
+
+    pub fn next_id(&self) -> u16 {
+        self.categories.len() as u16
+    }
+}
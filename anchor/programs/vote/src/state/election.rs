@@ -1,50 +1,108 @@
 // state/election.rs
 
-use anchor_lang::prelude::*;
+use anchor_lang::{ prelude::*, Discriminator };
 
 use crate::D21Error;
 
-use super::{ Candidate, ElectionStatus, UserType };
+use super::{
+    Candidate,
+    CategoryRegistry,
+    EligibilityEvidence,
+    EligibilityRule,
+    ElectionStatus,
+    ElectionTombstone,
+    ElectionVoter,
+    Officer,
+    OfficerPermission,
+    RuleLogic,
+    Tally,
+    VotingMethod,
+};
 use crate::constant::*;
 
 #[account]
 #[derive(Default)]
 pub struct Election {
     // Admin data
+    pub organization: Pubkey,
     pub authority: Pubkey,
     pub bump: u8,
+    // Set by the authority and cleared once the new key accepts
+    pub pending_authority: Option<Pubkey>,
+    // Keys the authority has delegated scoped powers to
+    pub officers: Vec<Officer>,
+    // Committee-governed elections: admin actions need approval_threshold of these keys
+    pub committee: Vec<Pubkey>,
+    pub approval_threshold: u8,
+    pub proposal_count: u32,
 
     // Configuration
     pub id: String,
     pub name: String,
+    pub voting_method: VotingMethod,
     pub num_winners: u8,
     pub num_plus_votes: u8,
     pub num_minus_votes: u8,
+    pub allow_revote: bool,
+    pub secret_ballot: bool,
+    pub credit_budget: u32,
+    // Token-weighted elections: ballots count tokens of this mint held in escrow
+    pub vote_mint: Option<Pubkey>,
+    // NFT-gated elections: voters must hold an NFT verified in this collection
+    pub nft_collection: Option<Pubkey>,
+    pub one_vote_per_nft: bool,
+    // Allowlisted elections: Merkle root over (voter, weight) leaves
+    pub voter_merkle_root: Option<[u8; 32]>,
 
-    // Voter type restrictions
-    pub allowed_voter_types: Vec<UserType>,
+    // Voter category ids from the CategoryRegistry
+    pub allowed_voter_types: Vec<u16>,
+    // Checked when a verified user registers; defaults to the allowed voter types
+    pub eligibility_logic: RuleLogic,
+    pub eligibility_rules: Vec<EligibilityRule>,
 
     // Status
     pub status: ElectionStatus,
-    pub start_time: i64,
+    pub start_time: Option<i64>,
     pub end_time: Option<i64>,
 
     // Results
     pub total_voters: u32,
+    pub total_weight: u64,
+    pub revealed_ballots: u32,
     pub candidates: Vec<Candidate>,
     pub winners: Vec<Pubkey>,
 }
 
+// Everything about a new election beyond its id, name and candidates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ElectionConfig {
+    pub num_winners: u8,
+    pub num_plus_votes: u8,
+    pub num_minus_votes: u8,
+    pub allowed_voter_types: Vec<u16>,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    pub allow_revote: bool,
+    pub secret_ballot: bool,
+    pub voting_method: VotingMethod,
+    pub credit_budget: u32,
+    pub vote_mint: Option<Pubkey>,
+    pub nft_collection: Option<Pubkey>,
+    pub one_vote_per_nft: bool,
+    pub voter_merkle_root: Option<[u8; 32]>,
+}
+
 // Constants for space calculation
 impl Election {
     pub fn validate_config(
         &self,
         name: &str,
         candidates: &[Pubkey],
-        num_winners: u8,
-        num_plus_votes: u8,
-        num_minus_votes: u8
+        config: &ElectionConfig
     ) -> Result<()> {
+        let voting_method = &config.voting_method;
+        let num_winners = config.num_winners;
+
         // Validate name
         require!(!name.is_empty() && name.len() <= MAX_NAME_LENGTH, D21Error::InvalidNameLength);
 
@@ -60,47 +118,421 @@ impl Election {
         unique_candidates.dedup();
         require!(unique_candidates.len() == candidates.len(), D21Error::DuplicateCandidates);
 
-        // Validate winner count
+        // Validate winner count; instant runoff always elects a single winner
         require!(
             num_winners > 0 && num_winners < (candidates.len() as u8),
             D21Error::InvalidWinnerCount
         );
+        if *voting_method == VotingMethod::InstantRunoff {
+            require!(num_winners == 1, D21Error::InvalidWinnerCount);
+        }
 
-        // Validate vote counts using D21 formula
-        let max_plus = calculate_max_plus_votes(num_winners, candidates.len() as u8);
+        // Validate vote counts; num_plus_votes caps the selections (or ranks) per ballot,
+        // which can never exceed what a Ballot account holds
+        let max_plus = match voting_method {
+            VotingMethod::D21 => calculate_max_plus_votes(num_winners, candidates.len() as u8),
+            VotingMethod::Plurality => 1,
+            VotingMethod::Approval |
+            VotingMethod::Borda |
+            VotingMethod::InstantRunoff |
+            VotingMethod::SingleTransferable |
+            VotingMethod::Quadratic => {
+                std::cmp::min(candidates.len() as u8, MAX_PLUS_VOTES)
+            }
+        };
+        let (num_plus_votes, num_minus_votes) = (config.num_plus_votes, config.num_minus_votes);
         require!(num_plus_votes > 0 && num_plus_votes <= max_plus, D21Error::InvalidPlusVoteCount);
 
-        require!(num_minus_votes <= num_plus_votes / 3, D21Error::InvalidMinusVoteCount);
+        if voting_method.allows_minus_votes() {
+            require!(num_minus_votes <= num_plus_votes / 3, D21Error::InvalidMinusVoteCount);
+        } else {
+            require!(num_minus_votes == 0, D21Error::InvalidMinusVoteCount);
+        }
+
+        // Only quadratic elections hand out credits
+        require!(
+            (config.credit_budget > 0) == (*voting_method == VotingMethod::Quadratic),
+            D21Error::InvalidCreditBudget
+        );
 
         Ok(())
     }
 
     pub fn space(max_candidates: usize) -> usize {
         8 + // discriminator
+            32 + // organization
             32 + // authority
             1 + // bump
+            33 + // pending_authority option
+            4 +
+            MAX_OFFICERS * Officer::SPACE + // officers vec
+            4 +
+            MAX_COMMITTEE_MEMBERS * 32 + // committee vec
+            1 + // approval_threshold
+            4 + // proposal_count
             4 +
             UUID_LENGTH + // uuid string (max 32 chars)
             4 +
             MAX_NAME_LENGTH + // name string
+            1 + // voting_method enum
             1 + // num_winners
             1 + // num_plus_votesc
             1 + // num_minus_votes
+            1 + // allow_revote
+            1 + // secret_ballot
+            4 + // credit_budget
+            33 + // vote_mint option
+            33 + // nft_collection option
+            1 + // one_vote_per_nft
+            33 + // voter_merkle_root option
             4 +
-            2 * 2 + // allowed_voter_types vec (max 2 types)
+            MAX_ALLOWED_VOTER_TYPES * 2 + // allowed_voter_types vec
+            1 + // eligibility_logic enum
+            4 +
+            MAX_ELIGIBILITY_RULES * EligibilityRule::MAX_SIZE + // eligibility_rules vec
             1 + // status enum
-            8 + // start_time
+            9 + // start_time option
             9 + // end_time option
             4 + // total_voters
+            8 + // total_weight
+            4 + // revealed_ballots
             4 +
             max_candidates * std::mem::size_of::<Candidate>() + // candidates vec
             4 +
             max_candidates * 32 // winners vec
     }
     // Add method to check if user type is allowed
-    pub fn is_voter_type_allowed(&self, voter_type: &UserType) -> bool {
+    pub fn is_voter_type_allowed(&self, voter_type: &u16) -> bool {
         self.allowed_voter_types.contains(voter_type)
     }
+
+    pub fn validate_eligibility_rules(
+        rules: &[EligibilityRule],
+        categories: &CategoryRegistry
+    ) -> Result<()> {
+        require!(
+            !rules.is_empty() && rules.len() <= MAX_ELIGIBILITY_RULES,
+            D21Error::InvalidEligibilityRules
+        );
+        rules.iter().try_for_each(|rule| rule.validate(categories))
+    }
+
+    // The authority holds every permission; officers only the ones granted to them
+    pub fn can_act(&self, key: &Pubkey, permission: &OfficerPermission) -> bool {
+        *key == self.authority ||
+            self.officers
+                .iter()
+                .any(|officer| officer.key == *key && officer.can(permission))
+    }
+
+    pub fn is_committee_member(&self, key: &Pubkey) -> bool {
+        self.committee.contains(key)
+    }
+
+    pub fn check_eligibility(&self, evidence: &EligibilityEvidence) -> Result<()> {
+        self.eligibility_logic.evaluate(&self.eligibility_rules, evidence)
+    }
+
+    // Validate an optional schedule; scheduled start and end must be in the future
+    pub fn validate_schedule(start_time: Option<i64>, end_time: Option<i64>, now: i64) -> Result<()> {
+        if let Some(start) = start_time {
+            require!(start > now, D21Error::InvalidElectionTimes);
+        }
+        if let Some(end) = end_time {
+            require!(end > start_time.unwrap_or(now) && end > now, D21Error::InvalidElectionTimes);
+        }
+        Ok(())
+    }
+
+    pub fn has_started(&self, now: i64) -> bool {
+        self.start_time.is_some_and(|start| now >= start)
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        self.end_time.is_some_and(|end| now >= end)
+    }
+
+    // A scheduled start moves the election into Voting without the authority,
+    // and a scheduled end moves secret ballots into their reveal phase
+    pub fn current_status(&self, now: i64) -> ElectionStatus {
+        let mut status = self.status.clone();
+        if matches!(status, ElectionStatus::Created | ElectionStatus::Registration) && self.has_started(now) {
+            status = ElectionStatus::Voting;
+        }
+        if status == ElectionStatus::Voting && self.secret_ballot && self.has_ended(now) {
+            status = ElectionStatus::Reveal;
+        }
+        status
+    }
+
+    pub fn sync_status(&mut self, now: i64) {
+        self.status = self.current_status(now);
+    }
+
+    pub fn check_registration_open(&self, now: i64) -> Result<()> {
+        require!(
+            self.current_status(now) == ElectionStatus::Registration,
+            D21Error::RegistrationClosed
+        );
+        require!(!self.has_ended(now), D21Error::ElectionPeriodEnded);
+        Ok(())
+    }
+
+    pub fn check_voting_window(&self, now: i64) -> Result<()> {
+        require!(self.current_status(now) == ElectionStatus::Voting, D21Error::VotingNotOpen);
+        require!(!self.has_ended(now), D21Error::ElectionPeriodEnded);
+        Ok(())
+    }
+
+    pub fn check_reveal_window(&self, now: i64) -> Result<()> {
+        require!(self.current_status(now) == ElectionStatus::Reveal, D21Error::RevealNotOpen);
+        let reveal_closed = self.end_time.is_some_and(|end| now >= end + REVEAL_PERIOD);
+        require!(!reveal_closed, D21Error::ElectionPeriodEnded);
+        Ok(())
+    }
+
+    // Results may be cranked once voting and, for secret ballots, revealing are over
+    pub fn results_due(&self, now: i64) -> bool {
+        let grace = if self.secret_ballot { REVEAL_PERIOD } else { 0 };
+        self.end_time.is_some_and(|end| now >= end + grace)
+    }
+
+    // Whether the authority may end the election from its current phase
+    pub fn can_tally(&self) -> bool {
+        match self.status {
+            ElectionStatus::Voting => !self.secret_ballot,
+            ElectionStatus::Reveal | ElectionStatus::Tallying => true,
+            _ => false,
+        }
+    }
+
+    pub fn unrevealed_ballots(&self) -> u32 {
+        if self.secret_ballot { self.total_voters - self.revealed_ballots } else { 0 }
+    }
+
+    pub fn validate_ballot(&self, plus_votes: &[u8], minus_votes: &[u8]) -> Result<()> {
+        // Validate vote counts
+        require!(plus_votes.len() <= (self.num_plus_votes as usize), D21Error::TooManyPlusVotes);
+        require!(minus_votes.len() <= (self.num_minus_votes as usize), D21Error::TooManyMinusVotes);
+        match self.voting_method {
+            VotingMethod::D21 => {
+                require!(plus_votes.len() >= 2 || minus_votes.is_empty(), D21Error::InsufficientPlusVotes);
+            }
+            VotingMethod::Plurality => {
+                require!(plus_votes.len() == 1, D21Error::InvalidBallotForMethod);
+            }
+            VotingMethod::Approval |
+            VotingMethod::Borda |
+            VotingMethod::InstantRunoff |
+            VotingMethod::SingleTransferable => {
+                require!(!plus_votes.is_empty(), D21Error::InvalidBallotForMethod);
+            }
+            VotingMethod::Quadratic => {
+                // Quadratic ballots need weights and go through validate_quadratic_ballot
+                return err!(D21Error::InvalidBallotForMethod);
+            }
+        }
+
+        // Validate candidate indices
+        let candidate_count = self.candidates.len();
+        for &idx in plus_votes.iter().chain(minus_votes.iter()) {
+            require!((idx as usize) < candidate_count, D21Error::InvalidCandidate);
+        }
+
+        // Check for overlapping votes
+        for plus_idx in plus_votes.iter() {
+            require!(!minus_votes.contains(plus_idx), D21Error::OverlappingVotes);
+        }
+
+        // Check for duplicate votes
+        let mut all_votes = plus_votes.to_vec();
+        all_votes.extend(minus_votes.iter());
+        let mut vote_set = all_votes.clone();
+        vote_set.sort();
+        vote_set.dedup();
+        require!(vote_set.len() == all_votes.len(), D21Error::DuplicateVotes);
+
+        Ok(())
+    }
+
+    // Check a weighted ballot against the voter's credits and return its cost
+    pub fn validate_quadratic_ballot(
+        &self,
+        plus_votes: &[u8],
+        vote_weights: &[u16],
+        credits: u32
+    ) -> Result<u32> {
+        require!(self.voting_method == VotingMethod::Quadratic, D21Error::InvalidBallotForMethod);
+        require!(
+            !plus_votes.is_empty() && plus_votes.len() == vote_weights.len(),
+            D21Error::InvalidBallotForMethod
+        );
+        require!(plus_votes.len() <= (self.num_plus_votes as usize), D21Error::TooManyPlusVotes);
+
+        let candidate_count = self.candidates.len();
+        for &idx in plus_votes {
+            require!((idx as usize) < candidate_count, D21Error::InvalidCandidate);
+        }
+
+        let mut vote_set = plus_votes.to_vec();
+        vote_set.sort();
+        vote_set.dedup();
+        require!(vote_set.len() == plus_votes.len(), D21Error::DuplicateVotes);
+
+        // Each candidate's votes cost their square in credits
+        let mut cost: u64 = 0;
+        for &weight in vote_weights {
+            require!(weight > 0, D21Error::InvalidBallotForMethod);
+            cost += (weight as u64) * (weight as u64);
+        }
+        require!(cost <= (credits as u64), D21Error::InsufficientCredits);
+
+        Ok(cost as u32)
+    }
+
+    pub fn apply_quadratic_ballot(&mut self, plus_votes: &[u8], vote_weights: &[u16]) {
+        for (&idx, &weight) in plus_votes.iter().zip(vote_weights) {
+            self.candidates[idx as usize].plus_votes += weight as i64;
+        }
+    }
+
+    pub fn retract_quadratic_ballot(&mut self, plus_votes: &[u8], vote_weights: &[u16]) {
+        for (&idx, &weight) in plus_votes.iter().zip(vote_weights) {
+            self.candidates[idx as usize].plus_votes -= weight as i64;
+        }
+    }
+
+    // Voting power comes from the voter's registration (one, or their
+    // allowlist weight) unless the election is weighted by a token mint, in
+    // which case it is the amount the voter has locked in escrow
+    pub fn voting_weight(&self, election_voter: &ElectionVoter) -> Result<u64> {
+        if self.vote_mint.is_none() {
+            return Ok(election_voter.voting_power);
+        }
+        require!(election_voter.deposited_amount > 0, D21Error::NoVotingPower);
+        Ok(election_voter.deposited_amount)
+    }
+
+    // Add a validated ballot to the candidate counters, scaled by the ballot's
+    // voting weight; under Borda the plus counter accumulates points rather than votes
+    pub fn apply_ballot(&mut self, plus_votes: &[u8], minus_votes: &[u8], weight: u64) -> Result<()> {
+        let weight = i64::try_from(weight).map_err(|_| D21Error::VoteCountOverflow)?;
+        let num_candidates = self.candidates.len();
+        for (position, &idx) in plus_votes.iter().enumerate() {
+            let points = self.voting_method
+                .weight(position, num_candidates)
+                .checked_mul(weight)
+                .ok_or(D21Error::VoteCountOverflow)?;
+            let candidate = &mut self.candidates[idx as usize];
+            candidate.plus_votes = candidate.plus_votes
+                .checked_add(points)
+                .ok_or(D21Error::VoteCountOverflow)?;
+        }
+        for &idx in minus_votes {
+            let candidate = &mut self.candidates[idx as usize];
+            candidate.minus_votes = candidate.minus_votes
+                .checked_add(weight)
+                .ok_or(D21Error::VoteCountOverflow)?;
+        }
+        Ok(())
+    }
+
+    // Remove a previously applied ballot from the candidate counters
+    pub fn retract_ballot(&mut self, plus_votes: &[u8], minus_votes: &[u8], weight: u64) -> Result<()> {
+        let weight = i64::try_from(weight).map_err(|_| D21Error::VoteCountOverflow)?;
+        let num_candidates = self.candidates.len();
+        for (position, &idx) in plus_votes.iter().enumerate() {
+            let points = self.voting_method
+                .weight(position, num_candidates)
+                .checked_mul(weight)
+                .ok_or(D21Error::VoteCountOverflow)?;
+            let candidate = &mut self.candidates[idx as usize];
+            candidate.plus_votes = candidate.plus_votes
+                .checked_sub(points)
+                .ok_or(D21Error::VoteCountOverflow)?;
+        }
+        for &idx in minus_votes {
+            let candidate = &mut self.candidates[idx as usize];
+            candidate.minus_votes = candidate.minus_votes
+                .checked_sub(weight)
+                .ok_or(D21Error::VoteCountOverflow)?;
+        }
+        Ok(())
+    }
+
+    // Running total of the voting weight behind all counted ballots
+    pub fn add_weight(&mut self, weight: u64) -> Result<()> {
+        self.total_weight = self.total_weight
+            .checked_add(weight)
+            .ok_or(D21Error::VoteCountOverflow)?;
+        Ok(())
+    }
+
+    // Voter records may be closed once the election is final or has been closed
+    pub fn is_closed_or_final(election: &AccountInfo) -> Result<bool> {
+        require_keys_eq!(*election.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let data = election.try_borrow_data()?;
+        if data.starts_with(&ElectionTombstone::DISCRIMINATOR) {
+            return Ok(true);
+        }
+        let election = Election::try_deserialize(&mut &data[..])?;
+        Ok(election.status.is_terminal())
+    }
+
+    // Score used for ranking: net score under D21, votes or points otherwise
+    pub fn score(&self, candidate: &Candidate) -> i64 {
+        match self.voting_method {
+            VotingMethod::D21 => candidate.plus_votes - candidate.minus_votes,
+            VotingMethod::Plurality |
+            VotingMethod::Approval |
+            VotingMethod::Borda |
+            VotingMethod::InstantRunoff |
+            VotingMethod::SingleTransferable |
+            VotingMethod::Quadratic => candidate.plus_votes,
+        }
+    }
+
+    // Rank candidates by the method's score and record the winners
+    pub fn compute_results(&mut self) {
+        let mut candidates = self.candidates.clone();
+        candidates.sort_by(|a, b| {
+            let a_score = self.score(a);
+            let b_score = self.score(b);
+
+            // Sort by score (descending) and then by plus votes if tied
+            b_score.cmp(&a_score)
+                .then(b.plus_votes.cmp(&a.plus_votes))
+        });
+
+        // Update rankings and set winners
+        self.winners.clear();
+        for (i, candidate) in candidates.iter().enumerate() {
+            let idx = self.candidates
+                .iter()
+                .position(|c| c.address == candidate.address)
+                .unwrap();
+            self.candidates[idx].rank = i as u8;
+
+            if i < self.num_winners as usize {
+                self.winners.push(candidate.address);
+            }
+        }
+    }
+
+    // Record rankings from a completed tally
+    pub fn apply_tally_results(&mut self, tally: &Tally) {
+        self.winners.clear();
+        for (rank, &idx) in tally.final_order().iter().enumerate() {
+            self.candidates[idx as usize].rank = rank as u8;
+            if rank < self.num_winners as usize {
+                self.winners.push(self.candidates[idx as usize].address);
+            }
+        }
+    }
+
+    pub fn first_preferences(&self) -> Vec<i64> {
+        self.candidates.iter().map(|c| c.plus_votes).collect()
+    }
 }
 
 // Helper function for D21 formula
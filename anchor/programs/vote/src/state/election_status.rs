@@ -1,15 +1,38 @@
 // election_status.rs
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
 pub enum ElectionStatus {
-    Created,
-    Active,
-    Ended,
+    #[default]
+    Created, // Being set up by the authority
+    Registration, // Voters may register, no ballots yet
+    Voting, // Voter roll frozen, ballots accepted
+    Reveal, // Secret ballots only: commitments are opened
+    Tallying, // Ballots closed, awaiting results
+    Ended, // Results final
+    Cancelled, // Aborted by the authority, no results
 }
 
-impl Default for ElectionStatus {
-    fn default() -> Self {
-        ElectionStatus::Created
+impl ElectionStatus {
+    // Transitions the authority may trigger directly; Ended and Cancelled have their own instructions
+    pub fn allowed_transitions(&self) -> Vec<ElectionStatus> {
+        match self {
+            ElectionStatus::Created => vec![ElectionStatus::Registration],
+            ElectionStatus::Registration => vec![ElectionStatus::Voting],
+            ElectionStatus::Voting => vec![ElectionStatus::Reveal, ElectionStatus::Tallying],
+            ElectionStatus::Reveal => vec![ElectionStatus::Tallying],
+            ElectionStatus::Tallying => vec![],
+            ElectionStatus::Ended => vec![],
+            ElectionStatus::Cancelled => vec![],
+        }
+    }
+
+    // No further changes are possible once an election is ended or cancelled
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ElectionStatus::Ended | ElectionStatus::Cancelled)
+    }
+
+    pub fn can_transition_to(&self, new_status: &ElectionStatus) -> bool {
+        self.allowed_transitions().contains(new_status)
     }
 }
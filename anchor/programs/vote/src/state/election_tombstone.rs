@@ -0,0 +1,27 @@
+// state/election_tombstone.rs
+
+use anchor_lang::prelude::*;
+
+use crate::constant::UUID_LENGTH;
+
+// What is left of an election once its authority closes it. It keeps the
+// election's address taken, so the id can't be initialised again while old
+// voter records and ballots still point at that address
+#[account]
+#[derive(Default)]
+pub struct ElectionTombstone {
+    pub organization: Pubkey,
+    pub authority: Pubkey,
+    pub id: String,
+    pub closed_at: i64,
+}
+
+impl ElectionTombstone {
+    pub const SPACE: usize =
+        8 + // discriminator
+        32 + // organization
+        32 + // authority
+        4 +
+        UUID_LENGTH + // id string
+        8; // closed_at
+}
@@ -0,0 +1,173 @@
+// state/eligibility.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{ Metadata, MetadataAccount };
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{ allowlist_leaf, verify_merkle_proof, D21Error, VerificationError, VoterError };
+use crate::constant::MAX_ALLOWED_VOTER_TYPES;
+use super::{ CategoryRegistry, UserVerification };
+
+// How an election's eligibility rules are combined
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+pub enum RuleLogic {
+    #[default]
+    All, // The voter must pass every rule
+    Any, // The voter must pass at least one rule
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum EligibilityRule {
+    UserType(Vec<u16>), // Verified in one of these voter categories
+    VerifiedFor(i64), // Verified at least this many seconds before registering
+    TokenBalance { mint: Pubkey, min_amount: u64 }, // Holds at least this many tokens in one account
+    NftHolder(Pubkey), // Holds an NFT verified in this collection
+    Allowlist([u8; 32]), // Has a proof for a weight-1 leaf under this Merkle root
+    RegistrarAttestation(Pubkey), // Verification was approved by this registrar
+}
+
+// What a voter brings to registration. Token and metadata accounts for the
+// TokenBalance and NftHolder rules are passed as remaining accounts
+pub struct EligibilityEvidence<'a, 'info> {
+    pub voter: Pubkey,
+    pub user_verification: &'a UserVerification,
+    pub allowlist_proof: &'a [[u8; 32]],
+    pub accounts: &'a [AccountInfo<'info>],
+    pub now: i64,
+}
+
+impl EligibilityRule {
+    pub const MAX_SIZE: usize =
+        1 + // variant
+        32 + // mint (TokenBalance is the largest variant)
+        8; // min_amount
+
+    // Voter categories must be ones the organisation currently offers
+    pub fn validate(&self, categories: &CategoryRegistry) -> Result<()> {
+        let valid = match self {
+            EligibilityRule::UserType(types) => {
+                !types.is_empty() && types.len() <= MAX_ALLOWED_VOTER_TYPES
+            }
+            EligibilityRule::VerifiedFor(seconds) => *seconds > 0,
+            EligibilityRule::TokenBalance { min_amount, .. } => *min_amount > 0,
+            _ => true,
+        };
+        require!(valid, D21Error::InvalidEligibilityRules);
+        if let EligibilityRule::UserType(types) = self {
+            require!(
+                types.iter().all(|id| categories.is_active(*id)),
+                VerificationError::UnknownCategory
+            );
+        }
+        Ok(())
+    }
+
+    // Fails with the error specific to this rule
+    pub fn check(&self, evidence: &EligibilityEvidence) -> Result<()> {
+        let verification = evidence.user_verification;
+        match self {
+            EligibilityRule::UserType(types) => {
+                require!(types.contains(&verification.user_type), D21Error::UserTypeNotAllowed);
+            }
+            EligibilityRule::VerifiedFor(seconds) => {
+                require!(
+                    evidence.now.saturating_sub(verification.verification_time) >= *seconds,
+                    VoterError::VerificationTooRecent
+                );
+            }
+            EligibilityRule::TokenBalance { mint, min_amount } => {
+                require!(
+                    evidence
+                        .token_accounts()
+                        .iter()
+                        .any(|account| account.mint == *mint && account.amount >= *min_amount),
+                    VoterError::InsufficientTokenBalance
+                );
+            }
+            EligibilityRule::NftHolder(collection) => {
+                require!(evidence.holds_collection_nft(collection), VoterError::NftNotHeld);
+            }
+            EligibilityRule::Allowlist(root) => {
+                let leaf = allowlist_leaf(&evidence.voter, 1);
+                require!(
+                    verify_merkle_proof(evidence.allowlist_proof, root, leaf),
+                    VoterError::InvalidAllowlistProof
+                );
+            }
+            EligibilityRule::RegistrarAttestation(registrar) => {
+                require!(
+                    verification.verified_by == Some(*registrar),
+                    VoterError::RegistrarAttestationMissing
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RuleLogic {
+    // Under Any, a voter who fails every rule gets the first rule's error
+    pub fn evaluate(&self, rules: &[EligibilityRule], evidence: &EligibilityEvidence) -> Result<()> {
+        let mut results = rules.iter().map(|rule| rule.check(evidence));
+        match self {
+            RuleLogic::All => results.try_for_each(|result| result),
+            RuleLogic::Any => {
+                let mut first_error = None;
+                for result in results {
+                    match result {
+                        Ok(()) => {
+                            return Ok(());
+                        }
+                        Err(error) => {
+                            first_error.get_or_insert(error);
+                        }
+                    }
+                }
+                Err(first_error.unwrap_or_else(|| D21Error::InvalidEligibilityRules.into()))
+            }
+        }
+    }
+}
+
+impl<'a, 'info> EligibilityEvidence<'a, 'info> {
+    // Token accounts among the evidence that belong to the voter
+    fn token_accounts(&self) -> Vec<TokenAccount> {
+        self.accounts
+            .iter()
+            .filter(|info| {
+                *info.owner == anchor_spl::token::ID || *info.owner == anchor_spl::token_2022::ID
+            })
+            .filter_map(|info| {
+                let data = info.try_borrow_data().ok()?;
+                TokenAccount::try_deserialize(&mut &data[..]).ok()
+            })
+            .filter(|account| account.owner == self.voter)
+            .collect()
+    }
+
+    // An NFT counts when its metadata account, at the Metaplex PDA for the
+    // mint, is also passed and names the collection as verified
+    fn holds_collection_nft(&self, collection: &Pubkey) -> bool {
+        self.token_accounts()
+            .iter()
+            .filter(|account| account.amount == 1)
+            .any(|account| {
+                let (metadata_key, _) = Pubkey::find_program_address(
+                    &[b"metadata", Metadata::id().as_ref(), account.mint.as_ref()],
+                    &Metadata::id()
+                );
+                self.accounts
+                    .iter()
+                    .filter(|info| info.key() == metadata_key && *info.owner == Metadata::id())
+                    .filter_map(|info| {
+                        let data = info.try_borrow_data().ok()?;
+                        MetadataAccount::try_deserialize(&mut &data[..]).ok()
+                    })
+                    .any(|metadata| {
+                        metadata.collection
+                            .as_ref()
+                            .is_some_and(|c| c.verified && c.key == *collection)
+                    })
+            })
+    }
+}
@@ -1,13 +1,33 @@
 mod ballot;
 mod election;
+mod election_tombstone;
 mod candidate;
 mod election_status;
 mod vote;
 mod verified_user;
+mod organization;
+mod proposal;
+mod officer;
+mod voting_method;
+mod tally;
+mod nft_claim;
+mod eligibility;
+mod category_registry;
+mod sponsorship;
 
 pub use ballot::*;
 pub use election::*;
+pub use election_tombstone::*;
 pub use candidate::*;
 pub use election_status::*;
 pub use vote::*;
 pub use verified_user::*;
+pub use organization::*;
+pub use proposal::*;
+pub use officer::*;
+pub use voting_method::*;
+pub use tally::*;
+pub use nft_claim::*;
+pub use eligibility::*;
+pub use category_registry::*;
+pub use sponsorship::*;
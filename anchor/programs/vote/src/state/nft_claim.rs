@@ -0,0 +1,29 @@
+// state/nft_claim.rs
+
+use anchor_lang::prelude::*;
+
+// Marks a membership NFT as used to register in an election, so the same
+// NFT can't be passed to another wallet and registered again
+#[account]
+#[derive(Default)]
+pub struct NftClaim {
+    pub election: Pubkey,
+    pub mint: Pubkey,
+    pub voter: Pubkey,
+    // Funded this account and gets its rent back on close
+    pub payer: Pubkey,
+    // Part of this account's rent the sponsorship covered; returned to it on close
+    pub sponsored_rent: u64,
+    pub bump: u8,
+}
+
+impl NftClaim {
+    pub const SPACE: usize =
+        8 + // discriminator
+        32 + // election
+        32 + // mint
+        32 + // voter
+        32 + // payer
+        8 + // sponsored_rent
+        1; // bump
+}
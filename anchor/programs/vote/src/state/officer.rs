@@ -0,0 +1,32 @@
+// state/officer.rs
+use anchor_lang::prelude::*;
+
+use crate::constant::MAX_OFFICER_PERMISSIONS;
+
+// Admin powers the authority can delegate to an election officer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum OfficerPermission {
+    UpdateVoterStatus,
+    UpdateElectionStatus,
+    EndElection,
+    CancelElection,
+    ManageVoterRoll,
+    VoidBallot,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct Officer {
+    pub key: Pubkey,
+    pub permissions: Vec<OfficerPermission>,
+}
+
+impl Officer {
+    pub const SPACE: usize =
+        32 + // key
+        4 +
+        MAX_OFFICER_PERMISSIONS; // permissions vec
+
+    pub fn can(&self, permission: &OfficerPermission) -> bool {
+        self.permissions.contains(permission)
+    }
+}
@@ -0,0 +1,68 @@
+// state/organization.rs
+use anchor_lang::prelude::*;
+
+use crate::constant::{
+    MAX_ELECTION_CREATORS,
+    MAX_NAME_LENGTH,
+    MAX_ORG_ADMINS,
+    MAX_ORG_ID_LENGTH,
+    MAX_REGISTRARS,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum OrgRole {
+    Admin, // Manages roles and voter categories
+    Registrar, // Approves or rejects verification requests
+    ElectionCreator, // Creates elections under the organisation
+}
+
+// A tenant such as a university. Its elections, voter categories and
+// verifications are all derived under this account
+#[account]
+#[derive(Default)]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+    pub admins: Vec<Pubkey>,
+    pub registrars: Vec<Pubkey>,
+    pub election_creators: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl Organization {
+    pub const SPACE: usize =
+        8 + // discriminator
+        4 +
+        MAX_ORG_ID_LENGTH + // id
+        4 +
+        MAX_NAME_LENGTH + // name
+        4 +
+        MAX_ORG_ADMINS * 32 + // admins vec
+        4 +
+        MAX_REGISTRARS * 32 + // registrars vec
+        4 +
+        MAX_ELECTION_CREATORS * 32 + // election_creators vec
+        1; // bump
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admins.contains(key)
+    }
+
+    pub fn is_registrar(&self, key: &Pubkey) -> bool {
+        self.registrars.contains(key)
+    }
+
+    // Admins can always create elections
+    pub fn can_create_elections(&self, key: &Pubkey) -> bool {
+        self.is_admin(key) || self.election_creators.contains(key)
+    }
+
+    // The member list for a role and how many keys it can hold
+    pub fn members_mut(&mut self, role: &OrgRole) -> (&mut Vec<Pubkey>, usize) {
+        match role {
+            OrgRole::Admin => (&mut self.admins, MAX_ORG_ADMINS),
+            OrgRole::Registrar => (&mut self.registrars, MAX_REGISTRARS),
+            OrgRole::ElectionCreator => (&mut self.election_creators, MAX_ELECTION_CREATORS),
+        }
+    }
+}
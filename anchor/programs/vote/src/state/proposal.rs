@@ -0,0 +1,53 @@
+// state/proposal.rs
+use anchor_lang::prelude::*;
+
+use crate::constant::{ MAX_COMMITTEE_MEMBERS, MAX_REASON_LENGTH };
+use super::{ ElectionStatus, StatusReason, VoterStatus };
+
+// Admin actions a committee-governed election can only take through a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum AdminAction {
+    UpdateElectionStatus(ElectionStatus),
+    EndElection,
+    CancelElection(String),
+    UpdateVoterStatus { voter: Pubkey, new_status: VoterStatus, reason: StatusReason },
+    VoidBallot { voter: Pubkey, reason: StatusReason },
+}
+
+#[account]
+pub struct Proposal {
+    pub election: Pubkey,
+    pub index: u32, // Position in the election's proposal sequence
+    pub proposer: Pubkey, // Paid for the account and gets the rent back
+    pub action: AdminAction,
+    pub approvals: Vec<Pubkey>, // Committee members who approved, proposer included
+    pub executed: bool,
+    pub created_at: i64,
+    pub expires_at: i64, // Stale proposals can no longer be approved or executed
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SPACE: usize =
+        8 + // discriminator
+        32 + // election
+        4 + // index
+        32 + // proposer
+        1 +
+        4 +
+        MAX_REASON_LENGTH + // action (CancelElection is the largest variant)
+        4 +
+        MAX_COMMITTEE_MEMBERS * 32 + // approvals vec
+        1 + // executed
+        8 + // created_at
+        8 + // expires_at
+        1; // bump
+
+    pub fn has_approved(&self, member: &Pubkey) -> bool {
+        self.approvals.contains(member)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -0,0 +1,24 @@
+// state/sponsorship.rs
+
+use anchor_lang::prelude::*;
+
+// Election-level vault of lamports that pays back whoever funded a voter's
+// registration or ballot rent, up to a per-voter cap
+#[account]
+#[derive(Default)]
+pub struct Sponsorship {
+    pub election: Pubkey,
+    // Most a single voter's rent can be reimbursed across all their accounts
+    pub per_voter_cap: u64,
+    pub total_reimbursed: u64,
+    pub bump: u8,
+}
+
+impl Sponsorship {
+    pub const SPACE: usize =
+        8 + // discriminator
+        32 + // election
+        8 + // per_voter_cap
+        8 + // total_reimbursed
+        1; // bump
+}
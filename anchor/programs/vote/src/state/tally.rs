@@ -0,0 +1,235 @@
+// state/tally.rs
+
+use anchor_lang::prelude::*;
+
+use super::VotingMethod;
+use crate::constant::VOTE_SCALE;
+
+// Round-by-round state for elections whose results need more than one pass
+// over the ballots. Each round is counted across as many transactions as it
+// takes; ballots are fed in ascending address order so none is counted twice.
+// Ballot values are fixed point, scaled by VOTE_SCALE.
+#[account]
+#[derive(Default)]
+pub struct Tally {
+    pub election: Pubkey,
+    pub payer: Pubkey,
+    pub round: u8,
+
+    // Progress through the current round
+    pub cursor: Pubkey,
+    pub ballots_counted: u32,
+    pub exhausted: u32, // ballots with no remaining preference this round
+
+    // Per-candidate values for the current round
+    pub counts: Vec<u128>,
+    // Share of each ballot passed on when its candidate leaves the count:
+    // all of it for eliminated candidates, the surplus for elected ones
+    pub transfer_factors: Vec<u64>,
+    // Candidate indices in the order they left the count, elected or eliminated
+    pub removed: Vec<u8>,
+    pub elected: Vec<u8>,
+    pub eliminated: Vec<u8>,
+    pub quota: u128,
+    pub complete: bool,
+    pub bump: u8,
+}
+
+impl Tally {
+    pub fn space(num_candidates: usize) -> usize {
+        8 + // discriminator
+            32 + // election
+            32 + // payer
+            1 + // round
+            32 + // cursor
+            4 + // ballots_counted
+            4 + // exhausted
+            4 +
+            num_candidates * 16 + // counts vec
+            4 +
+            num_candidates * 8 + // transfer_factors vec
+            4 +
+            num_candidates + // removed vec
+            4 +
+            num_candidates + // elected vec
+            4 +
+            num_candidates + // eliminated vec
+            16 + // quota
+            1 + // complete
+            1 // bump
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn is_eliminated(&self, idx: u8) -> bool {
+        self.eliminated.contains(&idx)
+    }
+
+    pub fn is_hopeful(&self, idx: u8) -> bool {
+        !self.removed.contains(&idx)
+    }
+
+    fn hopeful(&self) -> Vec<u8> {
+        (0..self.counts.len() as u8).filter(|&idx| self.is_hopeful(idx)).collect()
+    }
+
+    // Replay a ballot through every candidate that has left the count so far
+    // and credit whatever value reaches a candidate still in the running
+    pub fn count_ballot(&mut self, ranking: &[u8], weight: u64) {
+        let mut value = (VOTE_SCALE as u128) * (weight as u128);
+        let mut holder = if ranking.is_empty() { None } else { Some(0) };
+
+        for (step, &removed) in self.removed.iter().enumerate() {
+            let Some(position) = holder else {
+                break;
+            };
+            if ranking[position] != removed {
+                continue;
+            }
+            value = (value * (self.transfer_factors[removed as usize] as u128)) / (VOTE_SCALE as u128);
+            holder = ranking
+                .iter()
+                .enumerate()
+                .skip(position + 1)
+                .find(|(_, idx)| !self.removed[..=step].contains(idx))
+                .map(|(next, _)| next);
+        }
+
+        match holder {
+            Some(position) if value > 0 => {
+                self.counts[ranking[position] as usize] += value;
+            }
+            _ => {
+                self.exhausted += 1;
+            }
+        }
+        self.ballots_counted += 1;
+    }
+
+    // Close the current round and, unless the count is complete, reset for
+    // the next pass over the ballots
+    pub fn close_round(&mut self, method: &VotingMethod, seats: usize, first_preferences: &[i64]) {
+        match method {
+            VotingMethod::SingleTransferable => self.close_transferable_round(seats, first_preferences),
+            _ => self.close_runoff_round(first_preferences),
+        }
+        if self.complete {
+            return;
+        }
+
+        self.round += 1;
+        self.restart_round();
+    }
+
+    // Discard the current round's progress so its ballots can be fed again
+    pub fn restart_round(&mut self) {
+        self.cursor = Pubkey::default();
+        self.ballots_counted = 0;
+        self.exhausted = 0;
+        self.counts.iter_mut().for_each(|count| {
+            *count = 0;
+        });
+    }
+
+    // Instant runoff: a majority of continuing ballots wins, otherwise the
+    // weakest candidate is eliminated
+    fn close_runoff_round(&mut self, first_preferences: &[i64]) {
+        let hopeful = self.hopeful();
+        let continuing: u128 = self.counts.iter().sum();
+
+        let leader = self.leader(&hopeful, first_preferences);
+        if hopeful.len() == 1 || self.counts[leader as usize] * 2 > continuing {
+            self.elect(leader, VOTE_SCALE);
+            self.complete = true;
+            return;
+        }
+
+        self.eliminate(self.weakest(&hopeful, first_preferences));
+    }
+
+    // Single transferable vote: the first round fixes the Droop quota; each
+    // round elects the leader if they reach it, passing on their surplus by
+    // the Gregory method, or else eliminates the weakest candidate
+    fn close_transferable_round(&mut self, seats: usize, first_preferences: &[i64]) {
+        if self.round == 0 {
+            let valid_votes = self.counts.iter().sum::<u128>() / (VOTE_SCALE as u128);
+            self.quota = (valid_votes / ((seats as u128) + 1) + 1) * (VOTE_SCALE as u128);
+        }
+
+        let hopeful = self.hopeful();
+        let leader = self.leader(&hopeful, first_preferences);
+        let leader_count = self.counts[leader as usize];
+        if leader_count >= self.quota {
+            let surplus = leader_count - self.quota;
+            let factor = ((surplus * (VOTE_SCALE as u128)) / leader_count) as u64;
+            self.elect(leader, factor);
+        } else {
+            self.eliminate(self.weakest(&hopeful, first_preferences));
+        }
+
+        // Fill the remaining seats once there are no more candidates than seats
+        let hopeful = self.hopeful();
+        let seats_left = seats - self.elected.len();
+        if seats_left == 0 {
+            self.complete = true;
+        } else if hopeful.len() <= seats_left {
+            let mut remaining = hopeful;
+            remaining.sort_by(|&a, &b| self.counts[b as usize].cmp(&self.counts[a as usize]).then(a.cmp(&b)));
+            for idx in remaining {
+                self.elect(idx, VOTE_SCALE);
+            }
+            self.complete = true;
+        }
+    }
+
+    fn elect(&mut self, idx: u8, transfer_factor: u64) {
+        self.transfer_factors[idx as usize] = transfer_factor;
+        self.removed.push(idx);
+        self.elected.push(idx);
+    }
+
+    fn eliminate(&mut self, idx: u8) {
+        self.removed.push(idx);
+        self.eliminated.push(idx);
+    }
+
+    // Ties go to the candidate with more first preferences, then the lower index
+    fn leader(&self, hopeful: &[u8], first_preferences: &[i64]) -> u8 {
+        *hopeful
+            .iter()
+            .max_by(|&&a, &&b| {
+                self.counts[a as usize]
+                    .cmp(&self.counts[b as usize])
+                    .then(first_preferences[a as usize].cmp(&first_preferences[b as usize]))
+                    .then(b.cmp(&a))
+            })
+            .unwrap()
+    }
+
+    // Ties go to the candidate with fewer first preferences, then the higher index
+    fn weakest(&self, hopeful: &[u8], first_preferences: &[i64]) -> u8 {
+        *hopeful
+            .iter()
+            .min_by(|&&a, &&b| {
+                self.counts[a as usize]
+                    .cmp(&self.counts[b as usize])
+                    .then(first_preferences[a as usize].cmp(&first_preferences[b as usize]))
+                    .then(b.cmp(&a))
+            })
+            .unwrap()
+    }
+
+    // Final order: elected in order of election, candidates still standing by
+    // last-round count, then eliminated candidates from last out to first out
+    pub fn final_order(&self) -> Vec<u8> {
+        let mut standing = self.hopeful();
+        standing.sort_by(|&a, &b| self.counts[b as usize].cmp(&self.counts[a as usize]).then(a.cmp(&b)));
+
+        let mut order = self.elected.clone();
+        order.extend(standing);
+        order.extend(self.eliminated.iter().rev());
+        order
+    }
+}
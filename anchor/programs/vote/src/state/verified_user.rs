@@ -1,29 +1,33 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum UserType {
-    Student,
-    Staff,
-}
+use crate::constant::MAX_ID_NUMBER_LENGTH;
 
 #[account]
 pub struct UserVerification {
+    pub organization: Pubkey, // Organisation whose registrars review the request
     pub user: Pubkey, // The user's wallet address
+    pub payer: Pubkey, // Funded the request and gets the rent back if it is rejected
     pub id_number: String, // Student/Staff ID
-    pub user_type: UserType, // Student or Staff
-    pub is_verified: bool, // Verification status
-    pub verification_time: i64, // When they were verified
+    pub user_type: u16, // Category id from the CategoryRegistry
+    pub is_verified: bool, // Set once a registrar approves the request
+    pub request_time: i64, // When the user submitted the request
+    pub verification_time: i64, // When a registrar approved the request
+    pub verified_by: Option<Pubkey>, // Registrar who approved the request
     pub bump: u8, // PDA bump
 }
 
 impl UserVerification {
     pub const SPACE: usize =
         8 + // discriminator
+        32 + // organization
         32 + // pubkey
+        32 + // payer
         4 +
-        10 + // id_number (String with max 50 chars)
-        1 + // user_type (enum)
+        MAX_ID_NUMBER_LENGTH + // id_number (String with max 12 chars)
+        2 + // user_type (category id)
         1 + // is_verified
+        8 + // request_time
         8 + // verification_time
+        33 + // verified_by option
         1; // bump
 }
@@ -1,6 +1,10 @@
 // state/voter.rs
 use anchor_lang::prelude::*;
 
+use crate::constant::{ MAX_DELEGATORS, MAX_STATUS_HISTORY };
+use crate::VoterError;
+use super::Election;
+
 #[account]
 #[derive(Default)]
 pub struct ElectionVoter {
@@ -16,12 +20,41 @@ pub struct ElectionVoter {
     pub status: VoterStatus,
     // Has the voter cast their ballot in this election
     pub has_voted: bool,
+    // Quadratic voting credits left to spend
+    pub credits: u32,
+    // Token-weighted elections: amount locked in this voter's vault
+    pub deposited_amount: u64,
+    // NFT-gated elections: the membership NFT used to register
+    pub nft_mint: Option<Pubkey>,
+    // Weight of each ballot: 1, or the voter's allowlist weight
+    pub voting_power: u64,
+    // Voter whose ballot this voter's weight is cast with, if delegating
+    pub delegate: Option<Pubkey>,
+    // Number of voters delegating directly to this voter
+    pub delegator_count: u32,
+    // Number of voters whose weight reaches this voter, directly or along a chain
+    pub total_delegators: u32,
+    // Set when this voter's weight was cast through a delegate's ballot
+    pub voted_through: Option<Pubkey>,
+    // Authority or officer that put the voter on the roll, if not self-registered
+    pub registered_by: Option<Pubkey>,
+    // Rent reimbursed to this voter's payers from the election's sponsorship
+    pub sponsored_lamports: u64,
+    // Funded this account and gets its rent back on close
+    pub payer: Pubkey,
+    // Part of this account's rent the sponsorship covered; returned to it on close
+    pub sponsored_rent: u64,
+    // Nonce the voter's next off-chain signed ballot must carry
+    pub ballot_nonce: u64,
+    // Every status change and voided ballot, oldest first
+    pub status_history: Vec<StatusChange>,
     // Space for future updates
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
 pub enum VoterStatus {
+    #[default]
     Pending, // Initial state when registered
     Active, // Verified and can vote
     Suspended, // Temporarily blocked
@@ -29,12 +62,36 @@ pub enum VoterStatus {
     OnHold, // Under review/investigation
 }
 
-impl Default for VoterStatus {
-    fn default() -> Self {
-        VoterStatus::Pending
-    }
+// Why a voter's status changed or their ballot was voided
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum StatusReason {
+    Verified, // Identity or roll entry confirmed
+    Administrative, // Correction by the authority
+    Ineligible, // No longer meets the election's rules
+    Misconduct, // Breach of election rules
+    Fraud, // Fraudulent registration or ballot
+    Appeal, // Outcome of an appeal
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StatusChange {
+    pub from: VoterStatus,
+    pub to: VoterStatus, // Same as `from` when only the ballot was voided
+    pub reason: StatusReason,
+    pub ballot_voided: bool,
+    pub changed_by: Pubkey, // Authority, officer, or executed committee proposal
+    pub timestamp: i64,
+}
+
+impl StatusChange {
+    pub const SPACE: usize =
+        1 + // from
+        1 + // to
+        1 + // reason
+        1 + // ballot_voided
+        32 + // changed_by
+        8; // timestamp
+}
 
 impl ElectionVoter {
     pub const SPACE: usize =
@@ -45,8 +102,29 @@ impl ElectionVoter {
         1 + // is_eligible
         1 + // status
         1 + // has_voted
+        4 + // credits
+        8 + // deposited_amount
+        33 + // nft_mint option
+        8 + // voting_power
+        33 + // delegate option
+        4 + // delegator_count
+        4 + // total_delegators
+        33 + // voted_through option
+        33 + // registered_by option
+        8 + // sponsored_lamports
+        32 + // payer
+        8 + // sponsored_rent
+        8 + // ballot_nonce
+        4 +
+        MAX_STATUS_HISTORY * StatusChange::SPACE + // status_history vec, grown past this
         1;
 
+    // Room for MAX_STATUS_HISTORY entries is allocated up front; the account
+    // is reallocated as the history grows beyond that
+    pub fn space(history_len: usize) -> usize {
+        Self::SPACE + history_len.saturating_sub(MAX_STATUS_HISTORY) * StatusChange::SPACE
+    }
+
     pub fn allowed_transitions(&self) -> Vec<VoterStatus> {
         match self.status {
             VoterStatus::Pending =>
@@ -82,6 +160,40 @@ impl ElectionVoter {
     pub fn can_transition_to(&self, new_status: &VoterStatus) -> bool {
         self.allowed_transitions().contains(new_status)
     }
+
+    // Allowlist proofs and roll entries made by the authority stand in for the
+    // organisation's identity check; everyone else needs a verified identity
+    pub fn is_vouched_for(&self, election: &Election, has_user_verification: bool) -> bool {
+        election.voter_merkle_root.is_some() ||
+            self.registered_by.is_some() ||
+            has_user_verification
+    }
+
+    // The whole delegation tree rides along with the delegate's ballot, so its
+    // size is capped to fit in one transaction
+    pub fn add_delegators(&mut self, count: u32) -> Result<()> {
+        self.total_delegators = self.total_delegators
+            .checked_add(count)
+            .filter(|total| *total <= MAX_DELEGATORS)
+            .ok_or(VoterError::TooManyDelegators)?;
+        Ok(())
+    }
+
+    pub fn remove_delegators(&mut self, count: u32) {
+        self.total_delegators = self.total_delegators.saturating_sub(count);
+    }
+
+    // Only voters taken off the active roll can have a cast ballot voided;
+    // a voter on hold keeps their ballot until the review concludes
+    pub fn can_void_ballot(&self) -> bool {
+        matches!(self.status, VoterStatus::Suspended | VoterStatus::Revoked)
+    }
+
+    // Nothing is ever dropped; the account must already have room for the
+    // entry (see grow_status_history)
+    pub fn record(&mut self, change: StatusChange) {
+        self.status_history.push(change);
+    }
 }
 
 // Struct to manage state transitions
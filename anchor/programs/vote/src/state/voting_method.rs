@@ -0,0 +1,39 @@
+// voting_method.rs
+use anchor_lang::prelude::*;
+
+// Ballots keep the plus/minus shape for every method: minus votes are only
+// meaningful under D21, and Borda reads the plus list as a ranking
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+pub enum VotingMethod {
+    #[default]
+    D21, // Several plus votes and optional minus votes, ranked by net score
+    Plurality, // Exactly one plus vote, ranked by vote count
+    Approval, // Any number of plus votes, ranked by vote count
+    Borda, // Plus votes in order of preference, ranked by points
+    InstantRunoff, // Plus votes in order of preference, single winner by elimination rounds
+    SingleTransferable, // Plus votes in order of preference, proportional multi-winner by STV
+    Quadratic, // Weighted plus votes, each costing its weight squared from a credit budget
+}
+
+impl VotingMethod {
+    pub fn allows_minus_votes(&self) -> bool {
+        matches!(self, VotingMethod::D21)
+    }
+
+    // Methods whose results come from the multi-transaction tally crank
+    pub fn uses_tally(&self) -> bool {
+        matches!(self, VotingMethod::InstantRunoff | VotingMethod::SingleTransferable)
+    }
+
+    // Points a candidate receives for appearing at `position` on a ballot;
+    // ranked methods only track first preferences until they are tallied
+    pub fn weight(&self, position: usize, num_candidates: usize) -> i64 {
+        match self {
+            VotingMethod::Borda => (num_candidates - 1 - position) as i64,
+            VotingMethod::InstantRunoff | VotingMethod::SingleTransferable => {
+                (position == 0) as i64
+            }
+            _ => 1,
+        }
+    }
+}
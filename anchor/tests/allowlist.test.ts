@@ -0,0 +1,193 @@
+// tests/allowlist.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getCategoriesPDA,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+} from "./utils";
+
+// Mirrors merkle.rs: prefixed leaves and nodes, pairs hashed in sorted order
+const sha256 = (...parts: Buffer[]) =>
+  createHash("sha256").update(Buffer.concat(parts)).digest();
+
+const allowlistLeaf = (voter: PublicKey, weight: number) => {
+  const weightBytes = Buffer.alloc(8);
+  weightBytes.writeBigUInt64LE(BigInt(weight));
+  return sha256(Buffer.from([0]), voter.toBuffer(), weightBytes);
+};
+
+const hashPair = (a: Buffer, b: Buffer) =>
+  Buffer.compare(a, b) <= 0
+    ? sha256(Buffer.from([1]), a, b)
+    : sha256(Buffer.from([1]), b, a);
+
+// Returns the root and a proof for every leaf; odd nodes are carried up
+const buildTree = (leaves: Buffer[]) => {
+  const proofs: Buffer[][] = leaves.map(() => []);
+  let level = leaves.map((hash, i) => ({ hash, members: [i] }));
+  while (level.length > 1) {
+    const next = [];
+    for (let i = 0; i < level.length; i += 2) {
+      if (i + 1 === level.length) {
+        next.push(level[i]);
+        continue;
+      }
+      const [left, right] = [level[i], level[i + 1]];
+      left.members.forEach((m) => proofs[m].push(right.hash));
+      right.members.forEach((m) => proofs[m].push(left.hash));
+      next.push({
+        hash: hashPair(left.hash, right.hash),
+        members: [...left.members, ...right.members],
+      });
+    }
+    level = next;
+  }
+  return { root: level[0].hash, proofs };
+};
+
+describe("allowlist registration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let members: Keypair[];
+  let candidates: Keypair[];
+  const weights = [1, 5, 2];
+
+  const electionId = "roll-2024-01";
+  let electionPDA: PublicKey;
+  let tree: { root: Buffer; proofs: Buffer[][] };
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const register = (voter: Keypair, proof: Buffer[], weight: number) =>
+    program.methods
+      .registerAllowlistedVoter(
+        proof.map((p) => Array.from(p)),
+        new anchor.BN(weight)
+      )
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    members = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...members]);
+
+    tree = buildTree(
+      members.map((m, i) => allowlistLeaf(m.publicKey, weights[i]))
+    );
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      { voterMerkleRoot: Array.from(tree.root) }
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+  });
+
+  it("Should register members with their weight and let them vote", async () => {
+    await register(members[1], tree.proofs[1], weights[1]);
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", members[1].publicKey)
+    );
+    expect(electionVoter.votingPower.toString()).to.equal("5");
+
+    // No UserVerification account is needed to vote
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+    await program.methods
+      .vote(Buffer.from([2]), Buffer.from([]))
+      .accounts({
+        voter: members[1].publicKey,
+        payer: members[1].publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", members[1].publicKey),
+        electionVoter: pda("election_voter", members[1].publicKey),
+        userVerification: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([members[1]])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[2].plusVotes.toString()).to.equal("5");
+  });
+
+  it("Should reject a proof for a different weight", async () => {
+    try {
+      await register(members[0], tree.proofs[0], 10);
+
+      expect.fail("Should have thrown InvalidAllowlistProof error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidAllowlistProof");
+    }
+  });
+
+  it("Should refuse eligibility rules, which proofs would bypass", async () => {
+    try {
+      await program.methods
+        .setEligibilityRules({ all: {} }, [{ userType: { 0: [STUDENT] } }])
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown AllowlistHasNoRules error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AllowlistHasNoRules");
+    }
+  });
+
+  it("Should reject wallets that are not on the roll", async () => {
+    const outsider = Keypair.generate();
+    await fund(program, [outsider], 10);
+
+    try {
+      await register(outsider, tree.proofs[0], weights[0]);
+
+      expect.fail("Should have thrown InvalidAllowlistProof error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidAllowlistProof");
+    }
+  });
+});
@@ -0,0 +1,174 @@
+// tests/authority.test.ts
+import { Keypair, PublicKey } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import { createElection, fund, setElectionStatus } from "./utils";
+
+describe("election authority", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let successor: Keypair;
+  let officer: Keypair;
+  let candidates: Keypair[];
+
+  const electionId = "handover-2024-01";
+  let electionPDA: PublicKey;
+
+  const transferTo = (newAuthority: PublicKey | null) =>
+    program.methods
+      .transferAuthority(newAuthority)
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+  const accept = (signer: Keypair) =>
+    program.methods
+      .acceptAuthority()
+      .accounts({
+        newAuthority: signer.publicKey,
+        election: electionPDA,
+      })
+      .signers([signer])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    successor = Keypair.generate();
+    officer = Keypair.generate();
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, successor, officer]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+  });
+
+  it("Should hand the election over once the successor accepts", async () => {
+    await transferTo(successor.publicKey);
+
+    // Proposing alone changes nothing
+    let election = await program.account.election.fetch(electionPDA);
+    expect(election.authority.toString()).to.equal(authority.publicKey.toString());
+    expect(election.pendingAuthority.toString()).to.equal(
+      successor.publicKey.toString()
+    );
+
+    try {
+      await accept(officer);
+
+      expect.fail("Should have thrown NotPendingAuthority error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAuthority");
+    }
+
+    await accept(successor);
+
+    election = await program.account.election.fetch(electionPDA);
+    expect(election.authority.toString()).to.equal(successor.publicKey.toString());
+    expect(election.pendingAuthority).to.be.null;
+
+    await setElectionStatus(program, successor, electionPDA, {
+      registration: {},
+    });
+
+    try {
+      await setElectionStatus(program, authority, electionPDA, {
+        voting: {},
+      });
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("Should let the authority withdraw a pending transfer", async () => {
+    await transferTo(successor.publicKey);
+    await transferTo(null);
+
+    try {
+      await accept(successor);
+
+      expect.fail("Should have thrown NotPendingAuthority error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAuthority");
+    }
+  });
+
+  it("Should limit officers to the permissions they were granted", async () => {
+    await program.methods
+      .setOfficer(officer.publicKey, [{ updateElectionStatus: {} }])
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, officer, electionPDA, {
+      registration: {},
+    });
+    await setElectionStatus(program, officer, electionPDA, { voting: {} });
+
+    try {
+      await program.methods
+        .end()
+        .accounts({
+          authority: officer.publicKey,
+          election: electionPDA,
+        })
+        .signers([officer])
+        .rpc();
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    // Removing every permission removes the officer
+    await program.methods
+      .setOfficer(officer.publicKey, [])
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.officers).to.be.empty;
+  });
+
+  it("Should reject duplicate officer permissions", async () => {
+    try {
+      await program.methods
+        .setOfficer(officer.publicKey, [{ endElection: {} }, { endElection: {} }])
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidOfficerPermissions error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidOfficerPermissions");
+    }
+  });
+});
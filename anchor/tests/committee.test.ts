@@ -0,0 +1,279 @@
+// tests/committee.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  createElection,
+  fund,
+  getCategoriesPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+} from "./utils";
+
+describe("committee governance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let members: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "commission-2024-01";
+  let electionPDA: PublicKey;
+
+  const proposalPDA = (index: number) => {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), electionPDA.toBuffer(), indexBytes],
+      program.programId
+    )[0];
+  };
+
+  // Proposals stay open for an hour unless a lifetime is given
+  const propose = (
+    member: Keypair,
+    index: number,
+    action: any,
+    lifetime = 60 * 60
+  ) =>
+    program.methods
+      .proposeAction(
+        action,
+        new anchor.BN(Math.floor(Date.now() / 1000) + lifetime)
+      )
+      .accounts({
+        proposer: member.publicKey,
+        election: electionPDA,
+        proposal: proposalPDA(index),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([member])
+      .rpc();
+
+  const approve = (member: Keypair, index: number) =>
+    program.methods
+      .approveProposal()
+      .accounts({
+        member: member.publicKey,
+        election: electionPDA,
+        proposal: proposalPDA(index),
+      })
+      .signers([member])
+      .rpc();
+
+  const execute = (index: number) =>
+    program.methods
+      .executeProposal()
+      .accounts({
+        payer: provider.wallet.publicKey,
+        election: electionPDA,
+        proposal: proposalPDA(index),
+        electionVoter: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    members = Array(5)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...members]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    // Three of five members must agree on every admin action
+    await program.methods
+      .setCommittee(
+        members.map((m) => m.publicKey),
+        3
+      )
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("Should stop the authority from acting alone", async () => {
+    try {
+      await setElectionStatus(program, authority, electionPDA, {
+        registration: {},
+      });
+
+      expect.fail("Should have thrown CommitteeApprovalRequired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CommitteeApprovalRequired");
+    }
+  });
+
+  it("Should execute a proposal once the threshold is met", async () => {
+    await propose(members[0], 0, {
+      updateElectionStatus: { 0: { registration: {} } },
+    });
+    await approve(members[1], 0);
+
+    try {
+      await execute(0);
+
+      expect.fail("Should have thrown ThresholdNotMet error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ThresholdNotMet");
+    }
+
+    await approve(members[2], 0);
+    await execute(0);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ registration: {} });
+    expect(election.proposalCount).to.equal(1);
+
+    const proposal = await program.account.proposal.fetch(proposalPDA(0));
+    expect(proposal.executed).to.be.true;
+    expect(proposal.approvals.length).to.equal(3);
+
+    try {
+      await execute(0);
+
+      expect.fail("Should have thrown ProposalExecuted error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalExecuted");
+    }
+  });
+
+  it("Should cancel the election through the committee", async () => {
+    await propose(members[3], 0, {
+      cancelElection: { 0: "Candidate list was wrong" },
+    });
+    await approve(members[4], 0);
+    await approve(members[0], 0);
+    await execute(0);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it("Should keep the authority's other admin powers behind the committee", async () => {
+    const expectCommitteeRequired = async (request: Promise<string>) => {
+      try {
+        await request;
+
+        expect.fail("Should have thrown CommitteeApprovalRequired error");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("CommitteeApprovalRequired");
+      }
+    };
+
+    await expectCommitteeRequired(
+      program.methods
+        .transferAuthority(members[0].publicKey)
+        .accounts({ authority: authority.publicKey, election: electionPDA })
+        .signers([authority])
+        .rpc()
+    );
+    await expectCommitteeRequired(
+      program.methods
+        .setOfficer(members[0].publicKey, [{ manageVoterRoll: {} }])
+        .accounts({ authority: authority.publicKey, election: electionPDA })
+        .signers([authority])
+        .rpc()
+    );
+    await expectCommitteeRequired(
+      program.methods
+        .setEligibilityRules({ all: {} }, [])
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
+        })
+        .signers([authority])
+        .rpc()
+    );
+    await expectCommitteeRequired(
+      program.methods
+        .addToVoterRoll([members[0].publicKey])
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          {
+            pubkey: PublicKey.findProgramAddressSync(
+              [
+                Buffer.from("election_voter"),
+                electionPDA.toBuffer(),
+                members[0].publicKey.toBuffer(),
+              ],
+              program.programId
+            )[0],
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+        .signers([authority])
+        .rpc()
+    );
+  });
+
+  it("Should refuse stale proposals", async () => {
+    try {
+      await propose(members[0], 0, { endElection: {} }, 8 * 24 * 60 * 60);
+
+      expect.fail("Should have thrown InvalidProposalExpiry error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidProposalExpiry");
+    }
+
+    await propose(members[0], 0, { cancelElection: { 0: "Stale" } }, 4);
+    await approve(members[1], 0);
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    try {
+      await approve(members[2], 0);
+
+      expect.fail("Should have thrown ProposalExpired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProposalExpired");
+    }
+  });
+
+  it("Should reject proposals and approvals from outsiders", async () => {
+    try {
+      await propose(authority, 0, { endElection: {} });
+
+      expect.fail("Should have thrown NotCommitteeMember error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotCommitteeMember");
+    }
+
+    await propose(members[0], 0, { endElection: {} });
+
+    try {
+      await approve(members[0], 0);
+
+      expect.fail("Should have thrown AlreadyApproved error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AlreadyApproved");
+    }
+  });
+});
@@ -0,0 +1,288 @@
+// tests/delegation.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("vote delegation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets: alice, bob, carol and dave
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "assembly-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const verificationPDA = (key: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        key.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  // Other voters' ElectionVoter accounts, passed as remaining accounts
+  const voterAccounts = (indices: number[]) =>
+    indices.map((i) => ({
+      pubkey: pda("election_voter", voters[i].publicKey),
+      isSigner: false,
+      isWritable: true,
+    }));
+
+  const delegate = (from: number, to: number, chain: number[] = []) =>
+    program.methods
+      .delegateVote()
+      .accounts({
+        voter: voters[from].publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[from].publicKey),
+        delegateVoter: pda("election_voter", voters[to].publicKey),
+      })
+      .remainingAccounts(voterAccounts(chain))
+      .signers([voters[from]])
+      .rpc();
+
+  const revoke = (from: number, to: number, chain: number[] = []) =>
+    program.methods
+      .revokeDelegation()
+      .accounts({
+        voter: voters[from].publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[from].publicKey),
+        delegateVoter: pda("election_voter", voters[to].publicKey),
+      })
+      .remainingAccounts(voterAccounts(chain))
+      .signers([voters[from]])
+      .rpc();
+
+  const castVote = (i: number, plusVotes: number[], delegators: number[] = []) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from([]))
+      .accounts({
+        voter: voters[i].publicKey,
+        payer: voters[i].publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voters[i].publicKey),
+        electionVoter: pda("election_voter", voters[i].publicKey),
+        userVerification: verificationPDA(voters[i].publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(voterAccounts(delegators))
+      .signers([voters[i]])
+      .rpc();
+
+  // Verifies and registers new voters, appending them to `voters`
+  const addVoters = async (count: number) => {
+    const added = Array(count)
+      .fill(0)
+      .map(() => Keypair.generate());
+    await fund(program, added);
+
+    for (const voter of added) {
+      await verifyUser(
+        program,
+        authority,
+        voter,
+        `1705050${String(voters.length).padStart(2, "0")}`,
+        STUDENT
+      );
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: verificationPDA(voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+      voters.push(voter);
+    }
+  };
+
+  const electionVoter = (i: number) =>
+    program.account.electionVoter.fetch(
+      pda("election_voter", voters[i].publicKey)
+    );
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = [];
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    await addVoters(4);
+
+    // carol -> bob -> alice
+    await delegate(1, 0);
+    await delegate(2, 1, [0]);
+  });
+
+  it("Should refuse delegations that form a cycle", async () => {
+    try {
+      // alice -> carol would close the loop through bob
+      await delegate(0, 2, [1]);
+
+      expect.fail("Should have thrown DelegationCycle error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DelegationCycle");
+    }
+
+    try {
+      await delegate(0, 0);
+
+      expect.fail("Should have thrown SelfDelegation error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SelfDelegation");
+    }
+  });
+
+  it("Should cast the whole chain's weight with the delegate's ballot", async () => {
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    try {
+      await castVote(1, [1]);
+
+      expect.fail("Should have thrown VoteDelegated error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("VoteDelegated");
+    }
+
+    try {
+      // carol is missing from bob's side of the tree
+      await castVote(0, [0], [1]);
+
+      expect.fail("Should have thrown DelegatorsMissing error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DelegatorsMissing");
+    }
+
+    await castVote(0, [0], [1, 2]);
+    await castVote(3, [1]);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("3");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("1");
+    expect(election.totalVoters).to.equal(2);
+    expect(election.totalWeight.toString()).to.equal("4");
+
+    const carol = await electionVoter(2);
+    expect(carol.hasVoted).to.be.true;
+    expect(carol.votedThrough.toString()).to.equal(
+      voters[0].publicKey.toString()
+    );
+
+    try {
+      await revoke(2, 1, [0]);
+
+      expect.fail("Should have thrown AlreadyVoted error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+    }
+  });
+
+  it("Should let a delegator revoke and vote directly", async () => {
+    await revoke(2, 1, [0]);
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    await castVote(2, [2]);
+    await castVote(0, [0], [1]);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("2");
+    expect(election.candidates[2].plusVotes.toString()).to.equal("1");
+
+    const bob = await electionVoter(1);
+    expect(bob.delegatorCount).to.equal(0);
+    expect(bob.totalDelegators).to.equal(0);
+    expect((await electionVoter(0)).totalDelegators).to.equal(1);
+  });
+
+  it("Should count delegators along the whole chain", async () => {
+    expect((await electionVoter(0)).totalDelegators).to.equal(2);
+    expect((await electionVoter(0)).delegatorCount).to.equal(1);
+    expect((await electionVoter(1)).totalDelegators).to.equal(1);
+
+    try {
+      // Revoking must update alice, at the end of bob's chain, too
+      await revoke(2, 1);
+
+      expect.fail("Should have thrown DelegationChainBroken error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DelegationChainBroken");
+    }
+  });
+
+  it("Should cap a delegate's tree so its ballot still fits", async () => {
+    // alice already carries bob and carol; eight more reach the cap of ten
+    await addVoters(9);
+    for (let i = 4; i < 12; i++) {
+      await delegate(i, 0);
+    }
+    expect((await electionVoter(0)).totalDelegators).to.equal(10);
+
+    try {
+      await delegate(12, 0);
+
+      expect.fail("Should have thrown TooManyDelegators error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TooManyDelegators");
+    }
+
+    // A chain is refused the same way at its far end
+    try {
+      await delegate(12, 1, [0]);
+
+      expect.fail("Should have thrown TooManyDelegators error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TooManyDelegators");
+    }
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+    await castVote(
+      0,
+      [0],
+      Array.from({ length: 11 }, (_, i) => i + 1).filter((i) => i !== 3)
+    );
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("11");
+  });
+});
@@ -10,6 +10,15 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Vote } from "../target/types/vote";
 import { expect } from "chai";
+import {
+  STAFF,
+  STUDENT,
+  ensureOrganization,
+  getCategoriesPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
 
 describe("election-specific voter registration and voting", () => {
   const provider = anchor.AnchorProvider.env();
@@ -75,19 +84,27 @@ describe("election-specific voter registration and voting", () => {
       [electionPDA, electionBump] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("election"),
-          authority.publicKey.toBuffer(),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
           Buffer.from(electionId),
         ],
         program.programId
       );
 
       [voterVerificationPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_verification"), voter.publicKey.toBuffer()],
+        [
+          Buffer.from("user_verification"),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
+          voter.publicKey.toBuffer(),
+        ],
         program.programId
       );
 
       [voter2VerificationPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_verification"), voter2.publicKey.toBuffer()],
+        [
+          Buffer.from("user_verification"),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
+          voter2.publicKey.toBuffer(),
+        ],
         program.programId
       );
 
@@ -116,29 +133,42 @@ describe("election-specific voter registration and voting", () => {
           electionId,
           "Test Election",
           candidateKeys,
-          2, // num_winners
-          3, // num_plus_votes
-          1, // num_minus_votes
-          [{ student: {} }, { staff: {} }] // Allow both types for most tests
+          {
+            numWinners: 2,
+            numPlusVotes: 3,
+            numMinusVotes: 1,
+            allowedVoterTypes: [STUDENT, STAFF], // Allow both types for most tests
+            startTime: null,
+            endTime: null,
+            allowRevote: true,
+            secretBallot: false,
+            votingMethod: { d21: {} },
+            creditBudget: 0,
+            voteMint: null,
+            nftCollection: null,
+            oneVotePerNft: false,
+            voterMerkleRoot: null,
+          }
         )
         .accounts({
           authority: authority.publicKey,
+          organization: await ensureOrganization(program, authority),
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
           election: electionPDA,
           systemProgram: SystemProgram.programId,
         })
         .signers([authority])
         .rpc();
 
+      await setElectionStatus(program, authority, electionPDA, {
+        registration: {},
+      });
+
       // Verify voter as student by default
-      await program.methods
-        .verifyUser("170404021", { student: {} })
-        .accounts({
-          user: voter.publicKey,
-          userVerification: voterVerificationPDA,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([voter])
-        .rpc();
+      await verifyUser(program, authority, voter, "170404021", STUDENT);
     } catch (error) {
       console.error("Setup error:", error);
       throw error;
@@ -148,9 +178,11 @@ describe("election-specific voter registration and voting", () => {
   it("Successfully registers a verified voter for an election", async () => {
     try {
       const tx = await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: voterVerificationPDA,
@@ -184,9 +216,11 @@ describe("election-specific voter registration and voting", () => {
   it("Prevents registering same voter twice for same election", async () => {
     // Register voter first time
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -198,9 +232,11 @@ describe("election-specific voter registration and voting", () => {
     // Try to register again
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: voterVerificationPDA,
@@ -222,7 +258,7 @@ describe("election-specific voter registration and voting", () => {
     const [election2PDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from(election2Id),
       ],
       program.programId
@@ -243,24 +279,47 @@ describe("election-specific voter registration and voting", () => {
         election2Id,
         "Second Election",
         candidates.map((c) => c.publicKey),
-        2,
-        3,
-        1,
-        [{ student: {} }] // Only students allowed in second election
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT], // Only students allowed in second election
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: election2PDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, election2PDA, {
+      registration: {},
+    });
+
     // Register for first election
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -271,9 +330,11 @@ describe("election-specific voter registration and voting", () => {
 
     // Register for second election
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: election2PDA,
         electionVoter: electionVoter2PDA,
         userVerification: voterVerificationPDA,
@@ -299,9 +360,11 @@ describe("election-specific voter registration and voting", () => {
   it("Successfully updates voter status for an election", async () => {
     // First register the verified voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -312,11 +375,12 @@ describe("election-specific voter registration and voting", () => {
 
     // Update status to suspended
     const tx = await program.methods
-      .updateVoterStatus({ suspended: {} })
+      .updateVoterStatus({ suspended: {} }, { misconduct: {} })
       .accounts({
         authority: authority.publicKey,
         election: electionPDA,
         electionVoter: electionVoterPDA,
+        systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
@@ -327,14 +391,21 @@ describe("election-specific voter registration and voting", () => {
       electionVoterPDA
     );
     expect(electionVoter.status).to.deep.equal({ suspended: {} });
+    expect(electionVoter.isEligible).to.be.false;
+    expect(electionVoter.statusHistory.length).to.equal(1);
+    expect(electionVoter.statusHistory[0].reason).to.deep.equal({
+      misconduct: {},
+    });
   });
 
   it("Successfully casts vote with verified and registered voter", async () => {
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -346,11 +417,14 @@ describe("election-specific voter registration and voting", () => {
     // Cast votes
     const plusVotes = Buffer.from([0, 1, 2]);
     const minusVotes = Buffer.from([4]);
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
     const voteTx = await program.methods
       .vote(plusVotes, minusVotes)
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: ballotPDA,
         electionVoter: electionVoterPDA,
@@ -374,9 +448,11 @@ describe("election-specific voter registration and voting", () => {
   it("Prevents voting from suspended voter", async () => {
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -387,11 +463,12 @@ describe("election-specific voter registration and voting", () => {
 
     // Suspend voter
     await program.methods
-      .updateVoterStatus({ suspended: {} })
+      .updateVoterStatus({ suspended: {} }, { misconduct: {} })
       .accounts({
         authority: authority.publicKey,
         election: electionPDA,
         electionVoter: electionVoterPDA,
+        systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
@@ -400,11 +477,14 @@ describe("election-specific voter registration and voting", () => {
     try {
       const plusVotes = Buffer.from([0, 1]);
       const minusVotes = Buffer.from([4]);
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
       await program.methods
         .vote(plusVotes, minusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -422,15 +502,7 @@ describe("election-specific voter registration and voting", () => {
 
   it("Multiple voters of different types can register and vote in same election", async () => {
     // Set up second voter as staff
-    await program.methods
-      .verifyUser("170404053", { staff: {} })
-      .accounts({
-        user: voter2.publicKey,
-        userVerification: voter2VerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter2])
-      .rpc();
+    await verifyUser(program, authority, voter2, "170404053", STAFF);
 
     // Derive PDAs for second voter
     const [voter2ElectionVoterPDA] = PublicKey.findProgramAddressSync(
@@ -453,9 +525,11 @@ describe("election-specific voter registration and voting", () => {
 
     // Register first voter (student)
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -466,9 +540,11 @@ describe("election-specific voter registration and voting", () => {
 
     // Register second voter (staff)
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter2.publicKey,
+        payer: voter2.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: voter2ElectionVoterPDA,
         userVerification: voter2VerificationPDA,
@@ -477,11 +553,15 @@ describe("election-specific voter registration and voting", () => {
       .signers([voter2])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
     // First voter (student) votes
     await program.methods
       .vote(Buffer.from([0, 1]), Buffer.from([4]))
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: ballotPDA,
         electionVoter: electionVoterPDA,
@@ -496,6 +576,8 @@ describe("election-specific voter registration and voting", () => {
       .vote(Buffer.from([1, 2]), Buffer.from([3]))
       .accounts({
         voter: voter2.publicKey,
+        payer: voter2.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: voter2BallotPDA,
         electionVoter: voter2ElectionVoterPDA,
@@ -530,9 +612,11 @@ describe("election-specific voter registration and voting", () => {
   it("Prevents voting twice in same election", async () => {
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -544,11 +628,14 @@ describe("election-specific voter registration and voting", () => {
     // Cast first vote
     const plusVotes = Buffer.from([0, 1]);
     const minusVotes = Buffer.from([4]);
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
     await program.methods
       .vote(plusVotes, minusVotes)
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: ballotPDA,
         electionVoter: electionVoterPDA,
@@ -564,6 +651,8 @@ describe("election-specific voter registration and voting", () => {
         .vote(plusVotes, minusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -583,9 +672,11 @@ describe("election-specific voter registration and voting", () => {
   it("Prevents unauthorized status updates", async () => {
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -597,11 +688,12 @@ describe("election-specific voter registration and voting", () => {
     // Try to update status with wrong authority
     try {
       await program.methods
-        .updateVoterStatus({ suspended: {} })
+        .updateVoterStatus({ suspended: {} }, { misconduct: {} })
         .accounts({
           authority: voter.publicKey, // Using voter instead of authority
           election: electionPDA,
           electionVoter: electionVoterPDA,
+          systemProgram: SystemProgram.programId,
         })
         .signers([voter])
         .rpc();
@@ -613,6 +705,8 @@ describe("election-specific voter registration and voting", () => {
   });
 
   it("Prevents registering for ended election", async () => {
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
     // End the election first
     await program.methods
       .end()
@@ -626,9 +720,11 @@ describe("election-specific voter registration and voting", () => {
     // Try to register after election has ended
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: voterVerificationPDA,
@@ -637,9 +733,9 @@ describe("election-specific voter registration and voting", () => {
         .signers([voter])
         .rpc();
 
-      expect.fail("Should have thrown ElectionNotActive error");
+      expect.fail("Should have thrown RegistrationClosed error");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("ElectionNotActive");
+      expect(err.error.errorCode.code).to.equal("RegistrationClosed");
     }
   });
 
@@ -663,11 +759,14 @@ describe("election-specific voter registration and voting", () => {
         ],
         program.programId
       );
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
       await program.methods
         .vote(Buffer.from([0, 1]), Buffer.from([4]))
         .accounts({
           voter: voter2.publicKey,
+          payer: voter2.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: voter2BallotPDA,
           electionVoter: voter2ElectionVoterPDA,
@@ -686,9 +785,11 @@ describe("election-specific voter registration and voting", () => {
   it("Prevents invalid voter status transitions", async () => {
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: voterVerificationPDA,
@@ -700,11 +801,12 @@ describe("election-specific voter registration and voting", () => {
     // Try invalid transition from Active to Pending
     try {
       await program.methods
-        .updateVoterStatus({ pending: {} })
+        .updateVoterStatus({ pending: {} }, { administrative: {} })
         .accounts({
           authority: authority.publicKey,
           election: electionPDA,
           electionVoter: electionVoterPDA,
+          systemProgram: SystemProgram.programId,
         })
         .signers([authority])
         .rpc();
@@ -714,4 +816,171 @@ describe("election-specific voter registration and voting", () => {
       expect(err.error.errorCode.code).to.equal("InvalidStatusTransition");
     }
   });
+
+  it("Freezes the voter roll once voting opens", async () => {
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ voting: {} });
+    expect(election.startTime).to.not.be.null;
+
+    await verifyUser(program, authority, voter2, "170404053", STAFF);
+    const [voter2ElectionVoterPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("election_voter"),
+        electionPDA.toBuffer(),
+        voter2.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter2.publicKey,
+          payer: voter2.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          electionVoter: voter2ElectionVoterPDA,
+          userVerification: voter2VerificationPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter2])
+        .rpc();
+
+      expect.fail("Should have thrown RegistrationClosed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RegistrationClosed");
+    }
+  });
+
+  it("Prevents skipping election phases", async () => {
+    try {
+      await setElectionStatus(program, authority, electionPDA, {
+        tallying: {},
+      });
+
+      expect.fail("Should have thrown InvalidElectionTransition error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidElectionTransition");
+    }
+  });
+
+  it("Prevents non-authority from changing election phase", async () => {
+    try {
+      await setElectionStatus(program, voter, electionPDA, { voting: {} });
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("Lets a voter change their ballot while voting is open", async () => {
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    await program.methods
+      .vote(Buffer.from([0, 1]), Buffer.from([4]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: ballotPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    // Fix the misclick: swap candidate 1 for candidate 2 and drop the minus vote
+    await program.methods
+      .changeVote(Buffer.from([0, 2]), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        election: electionPDA,
+        userVerification: voterVerificationPDA,
+        electionVoter: electionVoterPDA,
+        ballot: ballotPDA,
+      })
+      .signers([voter])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.totalVoters).to.equal(1);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("1");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("0");
+    expect(election.candidates[2].plusVotes.toString()).to.equal("1");
+    expect(election.candidates[4].minusVotes.toString()).to.equal("0");
+
+    const ballot = await program.account.ballot.fetch(ballotPDA);
+    expect(Buffer.from(ballot.plusVotes)).to.deep.equal(Buffer.from([0, 2]));
+    expect(ballot.minusVotes.length).to.equal(0);
+  });
+
+  it("Prevents changing a ballot that was never cast", async () => {
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    try {
+      await program.methods
+        .changeVote(Buffer.from([0, 2]), Buffer.from([]))
+        .accounts({
+          voter: voter.publicKey,
+          election: electionPDA,
+          userVerification: voterVerificationPDA,
+          electionVoter: electionVoterPDA,
+          ballot: ballotPDA,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown BallotNotCast error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotNotCast");
+    }
+  });
 });
@@ -0,0 +1,225 @@
+// tests/eligibility.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import {
+  STAFF,
+  STUDENT,
+  createElection,
+  fund,
+  getCategoriesPDA,
+  getOrganizationPDA,
+  getVerificationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("eligibility rules", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voter: Keypair;
+  let candidates: Keypair[];
+
+  let electionPDA: PublicKey;
+  let electionCount = 0;
+
+  const setRules = (logic: any, rules: any[]) =>
+    program.methods
+      .setEligibilityRules(logic, rules)
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+      })
+      .signers([authority])
+      .rpc();
+
+  const register = (proof: number[][] = []) =>
+    program.methods
+      .registerVoter(proof)
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        userVerification: getVerificationPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey),
+          voter.publicKey
+        ),
+        electionVoter: PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("election_voter"),
+            electionPDA.toBuffer(),
+            voter.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, voter]);
+
+    const electionId = `rules-${electionCount++}`;
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      { allowedVoterTypes: [STUDENT, STAFF] }
+    );
+
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
+  });
+
+  it("Should default to the allowed voter types", async () => {
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.eligibilityLogic).to.deep.equal({ all: {} });
+    expect(election.eligibilityRules).to.deep.equal([
+      { userType: { 0: [STUDENT, STAFF] } },
+    ]);
+  });
+
+  it("Should report the rule a voter fails", async () => {
+    await setRules({ all: {} }, [
+      { userType: { 0: [STUDENT] } },
+      { verifiedFor: { 0: new anchor.BN(365 * 24 * 60 * 60) } },
+    ]);
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    try {
+      await register();
+
+      expect.fail("Should have thrown VerificationTooRecent error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("VerificationTooRecent");
+    }
+  });
+
+  it("Should admit a voter who passes any rule", async () => {
+    // The provider wallet approved the verification in verifyUser
+    await setRules({ any: {} }, [
+      { userType: { 0: [STAFF] } },
+      { registrarAttestation: { 0: provider.wallet.publicKey } },
+    ]);
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    await register();
+
+    const [electionVoterPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("election_voter"),
+        electionPDA.toBuffer(),
+        voter.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const electionVoter = await program.account.electionVoter.fetch(
+      electionVoterPDA
+    );
+    expect(electionVoter.isEligible).to.be.true;
+  });
+
+  it("Should require every rule under all-of logic", async () => {
+    // A single-leaf tree: the root is the weight-1 leaf itself
+    const weight = Buffer.alloc(8);
+    weight.writeBigUInt64LE(BigInt(1));
+    const root = createHash("sha256")
+      .update(Buffer.from([0]))
+      .update(voter.publicKey.toBuffer())
+      .update(weight)
+      .digest();
+
+    await setRules({ all: {} }, [
+      { allowlist: { 0: Array.from(root) } },
+      { registrarAttestation: { 0: Keypair.generate().publicKey } },
+    ]);
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    // The empty proof matches the single-leaf root, so the attestation fails
+    try {
+      await register([]);
+
+      expect.fail("Should have thrown RegistrarAttestationMissing error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RegistrarAttestationMissing");
+    }
+  });
+
+  it("Should lock the rules once registration opens", async () => {
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    try {
+      await setRules({ all: {} }, [{ userType: { 0: [STAFF] } }]);
+
+      expect.fail("Should have thrown EligibilityLocked error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EligibilityLocked");
+    }
+  });
+
+  it("Should reject rules naming a category the organisation doesn't offer", async () => {
+    try {
+      await setRules({ all: {} }, [{ userType: { 0: [STUDENT, 7] } }]);
+
+      expect.fail("Should have thrown UnknownCategory error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnknownCategory");
+    }
+  });
+
+  it("Should refuse an election for voter types the organisation doesn't offer", async () => {
+    try {
+      await createElection(
+        program,
+        authority,
+        `rules-${electionCount++}`,
+        candidates.map((c) => c.publicKey),
+        { allowedVoterTypes: [7] }
+      );
+
+      expect.fail("Should have thrown UnknownCategory error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnknownCategory");
+    }
+  });
+
+  it("Should reject an empty rule list", async () => {
+    try {
+      await setRules({ all: {} }, []);
+
+      expect.fail("Should have thrown InvalidEligibilityRules error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidEligibilityRules");
+    }
+  });
+});
@@ -0,0 +1,295 @@
+// tests/instant-runoff.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("instant runoff", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "irv-2024-01";
+  let electionPDA: PublicKey;
+  let tallyPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const verificationPDA = (key: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        key.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  // Every ballot, in the ascending address order the crank requires
+  const sortedBallots = () =>
+    voters
+      .map((v) => pda("ballot", v.publicKey))
+      .sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()));
+
+  const tallyBallots = (ballots: PublicKey[]) =>
+    program.methods
+      .tallyBallots()
+      .accounts({
+        election: electionPDA,
+        tally: tallyPDA,
+      })
+      .remainingAccounts(
+        ballots.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(5)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(4)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...voters]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      {
+        numPlusVotes: 4, // ranking depth
+        votingMethod: { instantRunoff: {} },
+      }
+    );
+    [tallyPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tally"), electionPDA.toBuffer()],
+      program.programId
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    for (const [i, voter] of voters.entries()) {
+      await verifyUser(program, authority, voter, `17040402${i}`, STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: verificationPDA(voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    // First preferences: 0, 0, 1, 2, 3
+    const rankings = [[0, 1], [0, 2], [1, 0], [2, 1], [3, 1]];
+    for (const [i, voter] of voters.entries()) {
+      await program.methods
+        .vote(Buffer.from(rankings[i]), Buffer.from([]))
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          ballot: pda("ballot", voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          userVerification: verificationPDA(voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+
+    await setElectionStatus(program, authority, electionPDA, {
+      tallying: {},
+    });
+  });
+
+  it("Should elect the majority winner after eliminations", async () => {
+    await program.methods
+      .startTally()
+      .accounts({
+        payer: authority.publicKey,
+        election: electionPDA,
+        tally: tallyPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Round 0: nobody has a majority, candidate 3 is eliminated
+    await tallyBallots(sortedBallots());
+    let tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.round).to.equal(1);
+    expect(Buffer.from(tally.eliminated)).to.deep.equal(Buffer.from([3]));
+
+    // A round can be split across transactions
+    const ballots = sortedBallots();
+    await tallyBallots(ballots.slice(0, 2));
+    await tallyBallots(ballots.slice(2));
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(Buffer.from(tally.eliminated)).to.deep.equal(Buffer.from([3, 2]));
+
+    // Round 2: candidate 1 collects transfers from 3 and 2
+    await tallyBallots(sortedBallots());
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.complete).to.be.true;
+    expect(Buffer.from(tally.elected)).to.deep.equal(Buffer.from([1]));
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ ended: {} });
+    expect(election.winners[0].toString()).to.equal(
+      candidates[1].publicKey.toString()
+    );
+    expect(election.candidates.map((c) => c.rank)).to.deep.equal([1, 0, 2, 3]);
+  });
+
+  it("Should reject ballots fed out of order", async () => {
+    await program.methods
+      .startTally()
+      .accounts({
+        payer: authority.publicKey,
+        election: electionPDA,
+        tally: tallyPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const ballots = sortedBallots();
+    await tallyBallots([ballots[0]]);
+
+    try {
+      await tallyBallots([ballots[0]]);
+
+      expect.fail("Should have thrown BallotOutOfOrder error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotOutOfOrder");
+    }
+  });
+
+  it("Should recover a round that skipped a ballot", async () => {
+    await program.methods
+      .startTally()
+      .accounts({
+        payer: authority.publicKey,
+        election: electionPDA,
+        tally: tallyPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Skipping a ballot leaves the round short, and it can't be fed late
+    const ballots = sortedBallots();
+    await tallyBallots([...ballots.slice(0, 2), ...ballots.slice(3)]);
+    let tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.round).to.equal(0);
+    expect(tally.ballotsCounted).to.equal(4);
+
+    try {
+      await tallyBallots([ballots[2]]);
+
+      expect.fail("Should have thrown BallotOutOfOrder error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotOutOfOrder");
+    }
+
+    // Only the tally's payer or the authority may discard the round's progress
+    try {
+      await program.methods
+        .restartTallyRound()
+        .accounts({
+          cranker: provider.wallet.publicKey,
+          election: electionPDA,
+          tally: tallyPDA,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await program.methods
+      .restartTallyRound()
+      .accounts({
+        cranker: authority.publicKey,
+        election: electionPDA,
+        tally: tallyPDA,
+      })
+      .signers([authority])
+      .rpc();
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.ballotsCounted).to.equal(0);
+
+    await tallyBallots(ballots);
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.round).to.equal(1);
+    expect(Buffer.from(tally.eliminated)).to.deep.equal(Buffer.from([3]));
+
+    // A round with nothing counted has nothing to restart
+    try {
+      await program.methods
+        .restartTallyRound()
+        .accounts({
+          cranker: authority.publicKey,
+          election: electionPDA,
+          tally: tallyPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown TallyRoundNotStarted error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TallyRoundNotStarted");
+    }
+  });
+
+  it("Should require the tally crank instead of end", async () => {
+    try {
+      await program.methods
+        .end()
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown TallyRequired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TallyRequired");
+    }
+  });
+});
@@ -0,0 +1,273 @@
+// tests/nft-gating.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  createAccount,
+  createMint,
+  mintTo,
+  transfer,
+} from "@solana/spl-token";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+// Metaplex Token Metadata program
+const METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// The validator loads a membership NFT mint and its metadata, verified in
+// this collection, from tests/fixtures; the keys come from fixed seeds
+const collection = Keypair.fromSeed(Buffer.alloc(32, "club-collection"))
+  .publicKey;
+const memberMint = Keypair.fromSeed(Buffer.alloc(32, "member-nft")).publicKey;
+const memberMintAuthority = Keypair.fromSeed(Buffer.alloc(32, "nft-gating"));
+
+describe("NFT-gated registration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voter: Keypair;
+  let candidates: Keypair[];
+
+  const electionId = "club-2024-01";
+  let electionPDA: PublicKey;
+  let electionVoterPDA: PublicKey;
+  let voterVerificationPDA: PublicKey;
+
+  const metadataPDA = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      METADATA_PROGRAM_ID
+    )[0];
+
+  const nftClaimPDA = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_claim"), electionPDA.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const registerNftVoter = (wallet: Keypair, nftTokenAccount: PublicKey) =>
+    program.methods
+      .registerNftVoter()
+      .accounts({
+        voter: wallet.publicKey,
+        payer: wallet.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        userVerification: PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("user_verification"),
+            getOrganizationPDA(program, authority.publicKey).toBuffer(),
+            wallet.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0],
+        electionVoter: PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("election_voter"),
+            electionPDA.toBuffer(),
+            wallet.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0],
+        nftMint: memberMint,
+        nftTokenAccount,
+        nftMetadata: metadataPDA(memberMint),
+        nftClaim: nftClaimPDA(memberMint),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  // Gives the wallet a token account holding one membership NFT
+  const issueMembership = async (wallet: Keypair) => {
+    const account = await createAccount(
+      provider.connection,
+      wallet,
+      memberMint,
+      wallet.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      wallet,
+      memberMint,
+      account,
+      memberMintAuthority,
+      1
+    );
+    return account;
+  };
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voter = Keypair.generate();
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, voter]);
+
+    electionPDA = getElectionPDA(program, authority.publicKey, electionId);
+    [electionVoterPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("election_voter"),
+        electionPDA.toBuffer(),
+        voter.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    [voterVerificationPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        voter.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      { nftCollection: collection, oneVotePerNft: true }
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
+  });
+
+  it("Should record the NFT gate on the election", async () => {
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.nftCollection.toString()).to.equal(collection.toString());
+    expect(election.oneVotePerNft).to.be.true;
+  });
+
+  it("Should register a holder of a verified collection NFT", async () => {
+    await registerNftVoter(voter, await issueMembership(voter));
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      electionVoterPDA
+    );
+    expect(electionVoter.voter.toString()).to.equal(voter.publicKey.toString());
+    expect(electionVoter.isEligible).to.be.true;
+
+    const claim = await program.account.nftClaim.fetch(
+      nftClaimPDA(memberMint)
+    );
+    expect(claim.mint.toString()).to.equal(memberMint.toString());
+    expect(claim.voter.toString()).to.equal(voter.publicKey.toString());
+  });
+
+  it("Should refuse a second wallet registering with the same NFT", async () => {
+    const nftTokenAccount = await issueMembership(voter);
+    await registerNftVoter(voter, nftTokenAccount);
+
+    // Hand the NFT to another verified wallet once it has been used
+    const other = Keypair.generate();
+    await fund(program, [other]);
+    await verifyUser(program, authority, other, "170404022", STUDENT);
+    const otherTokenAccount = await createAccount(
+      provider.connection,
+      other,
+      memberMint,
+      other.publicKey
+    );
+    await transfer(
+      provider.connection,
+      voter,
+      nftTokenAccount,
+      otherTokenAccount,
+      voter,
+      1
+    );
+
+    try {
+      await registerNftVoter(other, otherTokenAccount);
+
+      expect.fail("Should have refused an NFT that was already claimed");
+    } catch (err) {
+      expect(err.toString()).to.include("already in use");
+    }
+  });
+
+  it("Should refuse plain registration", async () => {
+    try {
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: voterVerificationPDA,
+          electionVoter: electionVoterPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown MembershipNftRequired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MembershipNftRequired");
+    }
+  });
+
+  it("Should refuse a token without collection metadata", async () => {
+    // A plain SPL token with supply 1 but no Metaplex metadata
+    const nftMint = await createMint(
+      provider.connection,
+      voter,
+      voter.publicKey,
+      null,
+      0
+    );
+    const nftTokenAccount = await createAccount(
+      provider.connection,
+      voter,
+      nftMint,
+      voter.publicKey
+    );
+    await mintTo(provider.connection, voter, nftMint, nftTokenAccount, voter, 1);
+
+    try {
+      await program.methods
+        .registerNftVoter()
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: voterVerificationPDA,
+          electionVoter: electionVoterPDA,
+          nftMint,
+          nftTokenAccount,
+          nftMetadata: metadataPDA(nftMint),
+          nftClaim: nftClaimPDA(nftMint),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown AccountNotInitialized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+});
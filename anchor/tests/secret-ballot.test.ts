@@ -0,0 +1,264 @@
+// tests/secret-ballot.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("secret ballots", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voter: Keypair;
+  let candidates: Keypair[];
+
+  // PDAs
+  const electionId = "secret-2024-01";
+  let electionPDA: PublicKey;
+  let electionVoterPDA: PublicKey;
+  let ballotPDA: PublicKey;
+  let voterVerificationPDA: PublicKey;
+
+  // Mirrors Ballot::commitment_for in the program
+  const commitmentFor = (
+    plusVotes: number[],
+    minusVotes: number[],
+    salt: Buffer
+  ): number[] => {
+    const hash = createHash("sha256")
+      .update(electionPDA.toBuffer())
+      .update(voter.publicKey.toBuffer())
+      .update(Buffer.from([plusVotes.length]))
+      .update(Buffer.from(plusVotes))
+      .update(Buffer.from([minusVotes.length]))
+      .update(Buffer.from(minusVotes))
+      .update(salt)
+      .digest();
+    return Array.from(hash);
+  };
+
+  beforeEach(async () => {
+    try {
+      authority = Keypair.generate();
+      voter = Keypair.generate();
+      candidates = Array(5)
+        .fill(0)
+        .map(() => Keypair.generate());
+
+      await fund(program, [authority, voter]);
+
+      // Derive PDAs
+      electionPDA = getElectionPDA(program, authority.publicKey, electionId);
+      [voterVerificationPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("user_verification"),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
+          voter.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      [electionVoterPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("election_voter"),
+          electionPDA.toBuffer(),
+          voter.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      [ballotPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("ballot"),
+          electionPDA.toBuffer(),
+          voter.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      // Secret-ballot election with one registered voter, voting open
+      await createElection(
+        program,
+        authority,
+        electionId,
+        candidates.map((c) => c.publicKey),
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          secretBallot: true,
+          votingMethod: { d21: {} },
+        }
+      );
+
+      await setElectionStatus(program, authority, electionPDA, {
+        registration: {},
+      });
+
+      await verifyUser(program, authority, voter, "170404021", STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: voterVerificationPDA,
+          electionVoter: electionVoterPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+    } catch (error) {
+      console.error("Setup error:", error);
+      throw error;
+    }
+  });
+
+  const commit = (commitment: number[]) =>
+    program.methods
+      .commitVote(commitment)
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: ballotPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const reveal = (plusVotes: number[], minusVotes: number[], salt: Buffer) =>
+    program.methods
+      .revealVote(
+        Buffer.from(plusVotes),
+        Buffer.from(minusVotes),
+        Array.from(salt)
+      )
+      .accounts({
+        voter: voter.publicKey,
+        election: electionPDA,
+        electionVoter: electionVoterPDA,
+        ballot: ballotPDA,
+      })
+      .signers([voter])
+      .rpc();
+
+  it("Hides the selection until it is revealed", async () => {
+    const salt = randomBytes(32);
+    await commit(commitmentFor([0, 1, 2], [4], salt));
+
+    // Nothing is tallied and the ballot holds only the commitment
+    let election = await program.account.election.fetch(electionPDA);
+    expect(election.totalVoters).to.equal(1);
+    election.candidates.forEach((c) => {
+      expect(c.plusVotes.toString()).to.equal("0");
+      expect(c.minusVotes.toString()).to.equal("0");
+    });
+    let ballot = await program.account.ballot.fetch(ballotPDA);
+    expect(ballot.plusVotes.length).to.equal(0);
+    expect(ballot.revealed).to.be.false;
+
+    await setElectionStatus(program, authority, electionPDA, { reveal: {} });
+    await reveal([0, 1, 2], [4], salt);
+
+    election = await program.account.election.fetch(electionPDA);
+    expect(election.revealedBallots).to.equal(1);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("1");
+    expect(election.candidates[4].minusVotes.toString()).to.equal("1");
+
+    ballot = await program.account.ballot.fetch(ballotPDA);
+    expect(Buffer.from(ballot.plusVotes)).to.deep.equal(Buffer.from([0, 1, 2]));
+    expect(ballot.revealed).to.be.true;
+  });
+
+  it("Rejects a reveal that does not match the commitment", async () => {
+    const salt = randomBytes(32);
+    await commit(commitmentFor([0, 1], [], salt));
+
+    await setElectionStatus(program, authority, electionPDA, { reveal: {} });
+
+    try {
+      await reveal([0, 2], [], salt);
+
+      expect.fail("Should have thrown InvalidReveal error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidReveal");
+    }
+  });
+
+  it("Rejects reveals during the voting phase", async () => {
+    const salt = randomBytes(32);
+    await commit(commitmentFor([0, 1], [], salt));
+
+    try {
+      await reveal([0, 1], [], salt);
+
+      expect.fail("Should have thrown RevealNotOpen error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RevealNotOpen");
+    }
+  });
+
+  it("Rejects plain votes in a secret-ballot election", async () => {
+    try {
+      await program.methods
+        .vote(Buffer.from([0, 1]), Buffer.from([]))
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          ballot: ballotPDA,
+          electionVoter: electionVoterPDA,
+          userVerification: voterVerificationPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown SecretBallotRequired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SecretBallotRequired");
+    }
+  });
+
+  it("Does not count unrevealed commitments when the election ends", async () => {
+    await commit(commitmentFor([0, 1], [], randomBytes(32)));
+
+    await setElectionStatus(program, authority, electionPDA, { reveal: {} });
+    await program.methods
+      .end()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ ended: {} });
+    expect(election.totalVoters).to.equal(1);
+    expect(election.revealedBallots).to.equal(0);
+    election.candidates.forEach((c) => {
+      expect(c.plusVotes.toString()).to.equal("0");
+    });
+  });
+});
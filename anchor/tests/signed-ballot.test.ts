@@ -0,0 +1,184 @@
+// tests/signed-ballot.test.ts
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  getVerificationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("signed ballots", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets; the voter never signs a transaction or holds SOL
+  let authority: Keypair;
+  let relayer: Keypair;
+  let voter: Keypair;
+  let candidates: Keypair[];
+
+  const electionId = "kiosk-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  // Mirrors Ballot::signed_message
+  const ballotMessage = (
+    nonce: number,
+    expiresAt: number,
+    plusVotes: number[],
+    minusVotes: number[]
+  ) => {
+    const nonceBytes = Buffer.alloc(8);
+    nonceBytes.writeBigUInt64LE(BigInt(nonce));
+    const expiryBytes = Buffer.alloc(8);
+    expiryBytes.writeBigInt64LE(BigInt(expiresAt));
+    return Buffer.concat([
+      Buffer.from("d21-ballot-v1"),
+      electionPDA.toBuffer(),
+      voter.publicKey.toBuffer(),
+      nonceBytes,
+      expiryBytes,
+      Buffer.from([plusVotes.length, ...plusVotes]),
+      Buffer.from([minusVotes.length, ...minusVotes]),
+    ]);
+  };
+
+  // The voter signs `signed` off-chain; the relayer submits `submitted`
+  const submit = (
+    signed: number[],
+    submitted: number[],
+    nonce = 0,
+    expiresAt = Math.floor(Date.now() / 1000) + 600
+  ) =>
+    program.methods
+      .submitSignedVote(
+        Buffer.from(submitted),
+        Buffer.from([]),
+        new anchor.BN(nonce),
+        new anchor.BN(expiresAt)
+      )
+      .accounts({
+        relayer: relayer.publicKey,
+        voter: voter.publicKey,
+        election: electionPDA,
+        userVerification: getVerificationPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey),
+          voter.publicKey
+        ),
+        electionVoter: pda("election_voter", voter.publicKey),
+        ballot: pda("ballot", voter.publicKey),
+        sponsorship: null,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: voter.secretKey,
+          message: ballotMessage(nonce, expiresAt, signed, []),
+        }),
+      ])
+      .signers([relayer])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    relayer = Keypair.generate();
+    voter = Keypair.generate();
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, relayer]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    await verifyUser(program, authority, voter, "170707021", STUDENT);
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: relayer.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        userVerification: getVerificationPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey),
+          voter.publicKey
+        ),
+        electionVoter: pda("election_voter", voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter, relayer])
+      .rpc();
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+  });
+
+  it("Should count a ballot the voter signed off-chain", async () => {
+    await submit([1], [1]);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[1].plusVotes.toString()).to.equal("1");
+    expect(election.totalVoters).to.equal(1);
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voter.publicKey)
+    );
+    expect(electionVoter.hasVoted).to.be.true;
+    expect(electionVoter.ballotNonce.toNumber()).to.equal(1);
+  });
+
+  it("Should reject a selection the voter did not sign", async () => {
+    try {
+      await submit([1], [2]);
+
+      expect.fail("Should have thrown InvalidBallotSignature error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBallotSignature");
+    }
+  });
+
+  it("Should reject stale nonces and expired ballots", async () => {
+    try {
+      await submit([1], [1], 1);
+
+      expect.fail("Should have thrown InvalidBallotNonce error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBallotNonce");
+    }
+
+    try {
+      await submit([1], [1], 0, Math.floor(Date.now() / 1000) - 600);
+
+      expect.fail("Should have thrown SignedBallotExpired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SignedBallotExpired");
+    }
+  });
+});
@@ -0,0 +1,197 @@
+// tests/single-transferable.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("single transferable vote", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "stv-2024-01";
+  let electionPDA: PublicKey;
+  let tallyPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const verificationPDA = (key: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        key.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  // Every ballot, in the ascending address order the crank requires
+  const sortedBallots = () =>
+    voters
+      .map((v) => pda("ballot", v.publicKey))
+      .sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()));
+
+  const tallyBallots = (ballots: PublicKey[]) =>
+    program.methods
+      .tallyBallots()
+      .accounts({
+        election: electionPDA,
+        tally: tallyPDA,
+      })
+      .remainingAccounts(
+        ballots.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(7)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(4)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...voters]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      {
+        numWinners: 2,
+        numPlusVotes: 4, // ranking depth
+        votingMethod: { singleTransferable: {} },
+      }
+    );
+    [tallyPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tally"), electionPDA.toBuffer()],
+      program.programId
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    for (const [i, voter] of voters.entries()) {
+      await verifyUser(program, authority, voter, `17040402${i}`, STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: verificationPDA(voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    // Seven ballots for two seats: the Droop quota is 7 / 3 + 1 = 3
+    const rankings = [[0, 1], [0, 1], [0, 1], [0, 2], [2], [3, 2], [1, 3]];
+    for (const [i, voter] of voters.entries()) {
+      await program.methods
+        .vote(Buffer.from(rankings[i]), Buffer.from([]))
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          ballot: pda("ballot", voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          userVerification: verificationPDA(voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+
+    await setElectionStatus(program, authority, electionPDA, {
+      tallying: {},
+    });
+  });
+
+  it("Should transfer surpluses and fill every seat", async () => {
+    await program.methods
+      .startTally()
+      .accounts({
+        payer: authority.publicKey,
+        election: electionPDA,
+        tally: tallyPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    // Round 0: candidate 0 has 4 votes against a quota of 3 and is elected,
+    // passing on a quarter of each of their ballots
+    await tallyBallots(sortedBallots());
+    let tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.quota.toString()).to.equal("3000000000");
+    expect(Buffer.from(tally.elected)).to.deep.equal(Buffer.from([0]));
+    expect(tally.transferFactors[0].toString()).to.equal("250000000");
+
+    // Round 1: 1 has 1.75, 2 has 1.25 and 3 has 1, so 3 is eliminated
+    await tallyBallots(sortedBallots());
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(Buffer.from(tally.eliminated)).to.deep.equal(Buffer.from([3]));
+
+    // Round 2: 3's ballot moves to 2, leaving 1 last; 2 takes the final seat
+    await tallyBallots(sortedBallots());
+    tally = await program.account.tally.fetch(tallyPDA);
+    expect(tally.complete).to.be.true;
+    expect(tally.counts[2].toString()).to.equal("2250000000");
+    expect(Buffer.from(tally.elected)).to.deep.equal(Buffer.from([0, 2]));
+    expect(Buffer.from(tally.eliminated)).to.deep.equal(Buffer.from([3, 1]));
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ ended: {} });
+    expect(election.winners.map((w) => w.toString())).to.deep.equal([
+      candidates[0].publicKey.toString(),
+      candidates[2].publicKey.toString(),
+    ]);
+    expect(election.candidates.map((c) => c.rank)).to.deep.equal([0, 2, 1, 3]);
+  });
+
+  it("Should require the tally crank instead of end", async () => {
+    try {
+      await program.methods
+        .end()
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown TallyRequired error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TallyRequired");
+    }
+  });
+});
@@ -0,0 +1,282 @@
+// tests/sponsorship.test.ts
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getElectionPDA,
+  getOrganizationPDA,
+  getVerificationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("sponsored voting", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets; the voter never holds any SOL
+  let authority: Keypair;
+  let relayer: Keypair;
+  let voter: Keypair;
+  let candidates: Keypair[];
+
+  const electionId = "sponsored-2024-01";
+  let electionPDA: PublicKey;
+  let sponsorshipPDA: PublicKey;
+  let registrationRent: number;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+  const register = () =>
+    program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: relayer.publicKey,
+        sponsorship: sponsorshipPDA,
+        election: electionPDA,
+        userVerification: getVerificationPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey),
+          voter.publicKey
+        ),
+        electionVoter: pda("election_voter", voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter, relayer])
+      .rpc();
+
+  const castVote = (plusVotes: number[]) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: relayer.publicKey,
+        sponsorship: sponsorshipPDA,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        electionVoter: pda("election_voter", voter.publicKey),
+        userVerification: getVerificationPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey),
+          voter.publicKey
+        ),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter, relayer])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    relayer = Keypair.generate();
+    voter = Keypair.generate();
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, relayer]);
+
+    electionPDA = getElectionPDA(program, authority.publicKey, electionId);
+    [sponsorshipPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsorship"), electionPDA.toBuffer()],
+      program.programId
+    );
+
+    await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    // Cap each voter at the rent of their registration
+    registrationRent =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        program.account.electionVoter.size
+      );
+
+    await program.methods
+      .createSponsorship(new anchor.BN(registrationRent))
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        sponsorship: sponsorshipPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .fundSponsorship(new anchor.BN(LAMPORTS_PER_SOL))
+      .accounts({
+        funder: authority.publicKey,
+        election: electionPDA,
+        sponsorship: sponsorshipPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    await verifyUser(program, authority, voter, "170606021", STUDENT);
+  });
+
+  it("Should reimburse the relayer up to the voter's cap", async () => {
+    const relayerBefore = await balance(relayer.publicKey);
+    await register();
+
+    // Registration rent came back in full from the vault
+    expect(await balance(relayer.publicKey)).to.equal(relayerBefore);
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    await castVote([0]);
+
+    // The cap is spent, so the relayer carries the ballot's rent itself
+    const ballot = await provider.connection.getAccountInfo(
+      pda("ballot", voter.publicKey)
+    );
+    expect(await balance(relayer.publicKey)).to.equal(
+      relayerBefore - ballot.lamports
+    );
+    expect(await balance(voter.publicKey)).to.equal(0);
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voter.publicKey)
+    );
+    expect(electionVoter.sponsoredLamports.toNumber()).to.equal(
+      registrationRent
+    );
+
+    const sponsorship = await program.account.sponsorship.fetch(sponsorshipPDA);
+    expect(sponsorship.totalReimbursed.toNumber()).to.equal(registrationRent);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("1");
+  });
+
+  it("Should return sponsored rent to the vault when accounts close", async () => {
+    await register();
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+    await castVote([0]);
+
+    await program.methods
+      .cancel("Sponsorship audit")
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const closeElectionVoter = (payer: PublicKey) =>
+      program.methods
+        .closeElectionVoter()
+        .accounts({
+          voter: voter.publicKey,
+          payer,
+          election: electionPDA,
+          electionVoter: pda("election_voter", voter.publicKey),
+          sponsorship: sponsorshipPDA,
+        })
+        .signers([voter])
+        .rpc();
+
+    // The voter can't redirect the refund to themselves
+    try {
+      await closeElectionVoter(voter.publicKey);
+
+      expect.fail("Should have thrown InvalidRentRecipient error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidRentRecipient");
+    }
+
+    // The vault covered the registration in full, so it takes all of it back
+    const relayerBefore = await balance(relayer.publicKey);
+    const vaultBefore = await balance(sponsorshipPDA);
+    await closeElectionVoter(relayer.publicKey);
+    expect(await balance(sponsorshipPDA)).to.equal(
+      vaultBefore + registrationRent
+    );
+    expect(await balance(relayer.publicKey)).to.equal(relayerBefore);
+
+    // The relayer paid for the ballot itself and gets that rent back
+    const ballotRent = await balance(pda("ballot", voter.publicKey));
+    await program.methods
+      .closeBallot()
+      .accounts({
+        voter: voter.publicKey,
+        payer: relayer.publicKey,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        sponsorship: sponsorshipPDA,
+      })
+      .signers([voter])
+      .rpc();
+    expect(await balance(relayer.publicKey)).to.equal(
+      relayerBefore + ballotRent
+    );
+    expect(await balance(sponsorshipPDA)).to.equal(
+      vaultBefore + registrationRent
+    );
+
+    // The vault has to be emptied before the election can be closed
+    const closeElection = () =>
+      program.methods
+        .closeElection()
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          sponsorship: sponsorshipPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+    try {
+      await closeElection();
+
+      expect.fail("Should have thrown SponsorshipStillOpen error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SponsorshipStillOpen");
+    }
+
+    const authorityBefore = await balance(authority.publicKey);
+    const vaultLeft = await balance(sponsorshipPDA);
+    await program.methods
+      .closeSponsorship()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        sponsorship: sponsorshipPDA,
+      })
+      .signers([authority])
+      .rpc();
+    expect(await balance(authority.publicKey)).to.equal(
+      authorityBefore + vaultLeft
+    );
+
+    await closeElection();
+    const tombstone = await program.account.electionTombstone.fetch(
+      electionPDA
+    );
+    expect(tombstone.id).to.equal(electionId);
+  });
+});
@@ -0,0 +1,268 @@
+// tests/token-weighted.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("token-weighted voting", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  // Token accounts
+  let mint: PublicKey;
+  let tokenAccounts: PublicKey[];
+
+  const electionId = "dao-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const verificationPDA = (key: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        key.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  const vaultPDA = (voter: Keypair) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), pda("election_voter", voter.publicKey).toBuffer()],
+      program.programId
+    )[0];
+
+  const castVote = (voter: Keypair, plusVotes: number[]) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        electionVoter: pda("election_voter", voter.publicKey),
+        userVerification: verificationPDA(voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const deposit = (i: number, amount: number | string) =>
+    program.methods
+      .depositTokens(new anchor.BN(amount))
+      .accounts({
+        voter: voters[i].publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[i].publicKey),
+        mint,
+        voterTokenAccount: tokenAccounts[i],
+        vault: vaultPDA(voters[i]),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voters[i]])
+      .rpc();
+
+  const withdraw = (i: number) =>
+    program.methods
+      .withdrawTokens()
+      .accounts({
+        voter: voters[i].publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[i].publicKey),
+        mint,
+        voterTokenAccount: tokenAccounts[i],
+        vault: vaultPDA(voters[i]),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([voters[i]])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(2)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...voters]);
+
+    // Governance token: the first voter holds 300, the second 50
+    mint = await createMint(
+      provider.connection,
+      authority,
+      authority.publicKey,
+      null,
+      0
+    );
+    tokenAccounts = [];
+    for (const [i, voter] of voters.entries()) {
+      const account = await createAccount(
+        provider.connection,
+        voter,
+        mint,
+        voter.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        authority,
+        mint,
+        account,
+        authority,
+        [300, 50][i]
+      );
+      tokenAccounts.push(account);
+    }
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      { voteMint: mint }
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    for (const [i, voter] of voters.entries()) {
+      await verifyUser(program, authority, voter, `17040402${i}`, STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: verificationPDA(voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+  });
+
+  it("Should weight ballots by escrowed tokens", async () => {
+    await deposit(0, 200);
+    await deposit(0, 100);
+    await deposit(1, 50);
+    await castVote(voters[0], [0]);
+    await castVote(voters[1], [1]);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.voteMint.toString()).to.equal(mint.toString());
+    expect(election.candidates[0].plusVotes.toString()).to.equal("300");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("50");
+    expect(election.totalVoters).to.equal(2);
+    expect(election.totalWeight.toString()).to.equal("350");
+
+    const ballot = await program.account.ballot.fetch(
+      pda("ballot", voters[0].publicKey)
+    );
+    expect(ballot.weight.toString()).to.equal("300");
+
+    // The tokens left the voter's wallet, so they can't back a second ballot
+    const wallet = await getAccount(provider.connection, tokenAccounts[0]);
+    expect(wallet.amount.toString()).to.equal("0");
+  });
+
+  it("Should refuse a deposit too large for the vote counters", async () => {
+    // 2^63 tokens no longer fit the signed candidate counters
+    const hugeAmount = "9223372036854775808";
+    await mintTo(
+      provider.connection,
+      authority,
+      mint,
+      tokenAccounts[1],
+      authority,
+      BigInt(hugeAmount)
+    );
+    await deposit(1, hugeAmount);
+
+    try {
+      await castVote(voters[1], [1]);
+
+      expect.fail("Should have thrown VoteCountOverflow error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("VoteCountOverflow");
+    }
+
+    // The failed ballot left the counters untouched
+    await deposit(0, 300);
+    await castVote(voters[0], [0]);
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("300");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("0");
+  });
+
+  it("Should require a deposit before voting", async () => {
+    try {
+      await castVote(voters[0], [0]);
+
+      expect.fail("Should have thrown NoVotingPower error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NoVotingPower");
+    }
+  });
+
+  it("Should lock deposits behind a ballot until the election ends", async () => {
+    await deposit(0, 300);
+    await castVote(voters[0], [0]);
+
+    try {
+      await withdraw(0);
+
+      expect.fail("Should have thrown TokensLocked error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TokensLocked");
+    }
+
+    await program.methods
+      .end()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+    await withdraw(0);
+
+    const wallet = await getAccount(provider.connection, tokenAccounts[0]);
+    expect(wallet.amount.toString()).to.equal("300");
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voters[0].publicKey)
+    );
+    expect(electionVoter.depositedAmount.toString()).to.equal("0");
+  });
+});
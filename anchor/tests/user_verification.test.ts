@@ -9,6 +9,19 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Vote } from "../target/types/vote";
 import { expect } from "chai";
+import {
+  STAFF,
+  STUDENT,
+  approveUser,
+  ensureOrganization,
+  getCategoriesPDA,
+  getOrganizationPDA,
+  getProgramDataPDA,
+  getVerificationPDA,
+  organizationId,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
 
 describe("user verification", () => {
   const provider = anchor.AnchorProvider.env();
@@ -21,6 +34,7 @@ describe("user verification", () => {
   let authority: Keypair;
 
   // PDAs
+  let organizationPDA: PublicKey;
   let userVerificationPDA: PublicKey;
   let verificationBump: number;
 
@@ -51,10 +65,16 @@ describe("user verification", () => {
       );
       await confirmTx(authorityAirdrop);
 
+      organizationPDA = await ensureOrganization(program, authority);
+
       // Derive PDA
       [userVerificationPDA, verificationBump] =
         PublicKey.findProgramAddressSync(
-          [Buffer.from("user_verification"), user.publicKey.toBuffer()],
+          [
+            Buffer.from("user_verification"),
+            getOrganizationPDA(program, authority.publicKey).toBuffer(),
+            user.publicKey.toBuffer(),
+          ],
           program.programId
         );
     } catch (error) {
@@ -67,17 +87,7 @@ describe("user verification", () => {
     try {
       const idNumber = "170404021";
 
-      const tx = await program.methods
-        .verifyUser(idNumber, { student: {} })
-        .accounts({
-          user: user.publicKey,
-          userVerification: userVerificationPDA,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([user])
-        .rpc();
-
-      await confirmTx(tx);
+      await verifyUser(program, authority, user, idNumber, STUDENT);
 
       const verificationAccount = await program.account.userVerification.fetch(
         userVerificationPDA
@@ -87,9 +97,12 @@ describe("user verification", () => {
         user.publicKey.toString()
       );
       expect(verificationAccount.idNumber).to.equal(idNumber);
-      expect(verificationAccount.userType).to.deep.equal({ student: {} });
+      expect(verificationAccount.userType).to.deep.equal(STUDENT);
       expect(verificationAccount.isVerified).to.be.true;
       expect(verificationAccount.verificationTime.toString()).to.not.equal("0");
+      expect(verificationAccount.verifiedBy.toString()).to.equal(
+        provider.wallet.publicKey.toString()
+      );
       expect(verificationAccount.bump).to.equal(verificationBump);
     } catch (error) {
       console.error("Test error:", error);
@@ -100,23 +113,13 @@ describe("user verification", () => {
   it("Successfully verifies a staff user with ID", async () => {
     const idNumber = "170404150";
 
-    const tx = await program.methods
-      .verifyUser(idNumber, { staff: {} })
-      .accounts({
-        user: user.publicKey,
-        userVerification: userVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([user])
-      .rpc();
-
-    await confirmTx(tx);
+    await verifyUser(program, authority, user, idNumber, STAFF);
 
     const verificationAccount = await program.account.userVerification.fetch(
       userVerificationPDA
     );
 
-    expect(verificationAccount.userType).to.deep.equal({ staff: {} });
+    expect(verificationAccount.userType).to.deep.equal(STAFF);
     expect(verificationAccount.idNumber).to.equal(idNumber);
     expect(verificationAccount.isVerified).to.be.true;
   });
@@ -125,15 +128,7 @@ describe("user verification", () => {
     try {
       const invalidId = "123456"; // 6 chars, minimum is 7
 
-      await program.methods
-        .verifyUser(invalidId, { student: {} })
-        .accounts({
-          user: user.publicKey,
-          userVerification: userVerificationPDA,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([user])
-        .rpc();
+      await verifyUser(program, authority, user, invalidId, STUDENT);
 
       expect.fail("Should have thrown InvalidIdNumber error");
     } catch (err) {
@@ -145,50 +140,301 @@ describe("user verification", () => {
     try {
       const invalidId = "1234567890123"; // 13 chars, maximum is 12
 
+      await verifyUser(program, authority, user, invalidId, STUDENT);
+
+      expect.fail("Should have thrown InvalidIdNumber error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidIdNumber");
+    }
+  });
+
+  it("Prevents duplicate verification", async () => {
+    // First verification
+    await verifyUser(program, authority, user, "170404021", STUDENT);
+
+    // Try to verify again
+    try {
+      await verifyUser(program, authority, user, "170404150", STUDENT);
+
+      expect.fail("Should have thrown account already exists error");
+    } catch (err) {
+      expect(err.toString()).to.include("custom program error: 0x0");
+      expect(err.toString()).to.include("already in use");
+    }
+  });
+
+  it("Keeps verification pending until a registrar approves it", async () => {
+    await program.methods
+      .requestVerification("170404021", STUDENT)
+      .accounts({
+        user: user.publicKey,
+        payer: user.publicKey,
+        organization: organizationPDA,
+        userVerification: userVerificationPDA,
+        categoryRegistry: getCategoriesPDA(program, organizationPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    let verificationAccount = await program.account.userVerification.fetch(
+      userVerificationPDA
+    );
+    expect(verificationAccount.isVerified).to.be.false;
+    expect(verificationAccount.verifiedBy).to.be.null;
+    expect(verificationAccount.requestTime.toString()).to.not.equal("0");
+
+    await approveUser(program, organizationPDA, user.publicKey);
+
+    verificationAccount = await program.account.userVerification.fetch(
+      userVerificationPDA
+    );
+    expect(verificationAccount.isVerified).to.be.true;
+    expect(verificationAccount.verifiedBy.toString()).to.equal(
+      provider.wallet.publicKey.toString()
+    );
+  });
+
+  it("Prevents non-registrars from approving verification", async () => {
+    await program.methods
+      .requestVerification("170404021", STUDENT)
+      .accounts({
+        user: user.publicKey,
+        payer: user.publicKey,
+        organization: organizationPDA,
+        userVerification: userVerificationPDA,
+        categoryRegistry: getCategoriesPDA(program, organizationPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    // The user cannot approve their own request
+    try {
       await program.methods
-        .verifyUser(invalidId, { student: {} })
+        .approveVerification()
         .accounts({
-          user: user.publicKey,
+          registrar: user.publicKey,
+          organization: organizationPDA,
+          payer: user.publicKey,
           userVerification: userVerificationPDA,
-          systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
 
-      expect.fail("Should have thrown InvalidIdNumber error");
+      expect.fail("Should have thrown NotRegistrar error");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("InvalidIdNumber");
+      expect(err.error.errorCode.code).to.equal("NotRegistrar");
     }
   });
 
-  it("Prevents duplicate verification", async () => {
-    // First verification
+  it("Closes rejected requests so the user can resubmit", async () => {
     await program.methods
-      .verifyUser("170404021", { student: {} })
+      .requestVerification("170404021", STAFF)
       .accounts({
         user: user.publicKey,
+        payer: user.publicKey,
+        organization: organizationPDA,
         userVerification: userVerificationPDA,
+        categoryRegistry: getCategoriesPDA(program, organizationPDA),
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
 
-    // Try to verify again
+    await program.methods
+      .rejectVerification()
+      .accounts({
+        registrar: provider.wallet.publicKey,
+        organization: organizationPDA,
+        payer: user.publicKey,
+        userVerification: userVerificationPDA,
+      })
+      .rpc();
+
+    const closed = await program.account.userVerification.fetchNullable(
+      userVerificationPDA
+    );
+    expect(closed).to.be.null;
+
+    // Resubmit with the correct user type
+    await verifyUser(program, authority, user, "170404021", STUDENT);
+
+    const verificationAccount = await program.account.userVerification.fetch(
+      userVerificationPDA
+    );
+    expect(verificationAccount.userType).to.deep.equal(STUDENT);
+    expect(verificationAccount.isVerified).to.be.true;
+  });
+
+  it("Only lets the upgrade authority create organisations", async () => {
+    // Claiming an id ahead of the institution it belongs to is refused
     try {
       await program.methods
-        .verifyUser("170404150", { student: {} })
+        .createOrganization(
+          organizationId(user.publicKey),
+          "Squatted University",
+          user.publicKey
+        )
         .accounts({
-          user: user.publicKey,
-          userVerification: userVerificationPDA,
+          upgradeAuthority: user.publicKey,
+          program: program.programId,
+          programData: getProgramDataPDA(program),
+          organization: getOrganizationPDA(program, user.publicKey),
           systemProgram: SystemProgram.programId,
         })
         .signers([user])
         .rpc();
 
-      expect.fail("Should have thrown account already exists error");
+      expect.fail("Should have thrown NotUpgradeAuthority error");
     } catch (err) {
-      expect(err.toString()).to.include("custom program error: 0x0");
-      expect(err.toString()).to.include("already in use");
+      expect(err.error.errorCode.code).to.equal("NotUpgradeAuthority");
+    }
+
+    // The upgrade authority hands the organisation to its first admin
+    const organization = await program.account.organization.fetch(
+      organizationPDA
+    );
+    expect(organization.admins.map((a) => a.toString())).to.deep.equal([
+      authority.publicKey.toString(),
+    ]);
+  });
+
+  it("Only lets organisation admins manage roles", async () => {
+    try {
+      await program.methods
+        .grantRole({ registrar: {} }, user.publicKey)
+        .accounts({
+          admin: user.publicKey,
+          organization: organizationPDA,
+        })
+        .signers([user])
+        .rpc();
+
+      expect.fail("Should have thrown NotOrgAdmin error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotOrgAdmin");
+    }
+
+    await program.methods
+      .grantRole({ electionCreator: {} }, user.publicKey)
+      .accounts({
+        admin: authority.publicKey,
+        organization: organizationPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    let organization = await program.account.organization.fetch(
+      organizationPDA
+    );
+    expect(organization.electionCreators.map((c) => c.toString())).to.include(
+      user.publicKey.toString()
+    );
+
+    await program.methods
+      .revokeRole({ electionCreator: {} }, user.publicKey)
+      .accounts({
+        admin: authority.publicKey,
+        organization: organizationPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    organization = await program.account.organization.fetch(organizationPDA);
+    expect(
+      organization.electionCreators.map((c) => c.toString())
+    ).to.not.include(user.publicKey.toString());
+
+    // The last admin cannot be removed
+    try {
+      await program.methods
+        .revokeRole({ admin: {} }, authority.publicKey)
+        .accounts({
+          admin: authority.publicKey,
+          organization: organizationPDA,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown LastAdmin error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("LastAdmin");
+    }
+  });
+
+  it("Keeps verifications separate per organisation", async () => {
+    const otherAdmin = Keypair.generate();
+    await confirmTx(
+      await provider.connection.requestAirdrop(
+        otherAdmin.publicKey,
+        100 * LAMPORTS_PER_SOL
+      )
+    );
+
+    await verifyUser(program, authority, user, "170404021", STUDENT);
+    await verifyUser(program, otherAdmin, user, "170404021", STAFF);
+
+    const first = await program.account.userVerification.fetch(
+      userVerificationPDA
+    );
+    const second = await program.account.userVerification.fetch(
+      getVerificationPDA(
+        program,
+        getOrganizationPDA(program, otherAdmin.publicKey),
+        user.publicKey
+      )
+    );
+    expect(first.userType).to.equal(STUDENT);
+    expect(second.userType).to.equal(STAFF);
+  });
+
+  it("Only lets election creators initialize elections", async () => {
+    const [electionPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("election"),
+        organizationPDA.toBuffer(),
+        Buffer.from("unauthorised"),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .initialize(
+          "unauthorised",
+          "Unauthorised Election",
+          Array(3) .fill(0) .map(() => Keypair.generate().publicKey),
+          {
+            numWinners: 1,
+            numPlusVotes: 1,
+            numMinusVotes: 0,
+            allowedVoterTypes: [STUDENT],
+            startTime: null,
+            endTime: null,
+            allowRevote: false,
+            secretBallot: false,
+            votingMethod: { plurality: {} },
+            creditBudget: 0,
+            voteMint: null,
+            nftCollection: null,
+            oneVotePerNft: false,
+            voterMerkleRoot: null,
+          }
+        )
+        .accounts({
+          authority: user.publicKey,
+          organization: organizationPDA,
+          election: electionPDA,
+          categoryRegistry: getCategoriesPDA(program, organizationPDA),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      expect.fail("Should have thrown NotElectionCreator error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotElectionCreator");
     }
   });
 
@@ -205,7 +451,7 @@ describe("user verification", () => {
     const [electionPDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from(electionId),
       ],
       program.programId
@@ -217,19 +463,40 @@ describe("user verification", () => {
         electionId,
         "Test Election",
         candidatePublicKeys, // Array of 5 candidate public keys
-        2, // num_winners
-        3, // num_plus_votes
-        1, // num_minus_votes,
-        [{ student: {} }]
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     const [electionVoterPDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election_voter"),
@@ -242,9 +509,11 @@ describe("user verification", () => {
     // Try to register without verification first
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: user.publicKey,
+          payer: user.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: userVerificationPDA,
@@ -259,21 +528,15 @@ describe("user verification", () => {
     }
 
     // Now verify the user
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: user.publicKey,
-        userVerification: userVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([user])
-      .rpc();
+    await verifyUser(program, authority, user, "170404021", STUDENT);
 
     // Try registration again after verification
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: user.publicKey,
+        payer: user.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: electionVoterPDA,
         userVerification: userVerificationPDA,
@@ -304,7 +567,7 @@ describe("user verification", () => {
     const [electionPDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from(electionId),
       ],
       program.programId
@@ -316,19 +579,40 @@ describe("user verification", () => {
         electionId,
         "Test Election",
         candidatePublicKeys,
-        2, // num_winners
-        3, // num_plus_votes
-        1, // num_minus_votes
-        [{ student: {} }] // Only allow students to vote
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT], // Only allow students to vote
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     const [electionVoterPDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election_voter"),
@@ -341,9 +625,11 @@ describe("user verification", () => {
     // Try to register without verification first
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: user.publicKey,
+          payer: user.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: userVerificationPDA,
@@ -358,22 +644,16 @@ describe("user verification", () => {
     }
 
     // Verify the user as staff (wrong type for this election)
-    await program.methods
-      .verifyUser("170404021", { staff: {} })
-      .accounts({
-        user: user.publicKey,
-        userVerification: userVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([user])
-      .rpc();
+    await verifyUser(program, authority, user, "170404021", STAFF);
 
     // Try registration with wrong user type
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: user.publicKey,
+          payer: user.publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: userVerificationPDA,
@@ -396,19 +676,15 @@ describe("user verification", () => {
     await confirmTx(studentAirdrop);
 
     const [studentVerificationPDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_verification"), studentUser.publicKey.toBuffer()],
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        studentUser.publicKey.toBuffer(),
+      ],
       program.programId
     );
 
-    await program.methods
-      .verifyUser("170404053", { student: {} })
-      .accounts({
-        user: studentUser.publicKey,
-        userVerification: studentVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([studentUser])
-      .rpc();
+    await verifyUser(program, authority, studentUser, "170404053", STUDENT);
 
     // Try registration with correct user type
     const [studentVoterPDA] = PublicKey.findProgramAddressSync(
@@ -421,9 +697,11 @@ describe("user verification", () => {
     );
 
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: studentUser.publicKey,
+        payer: studentUser.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: studentVoterPDA,
         userVerification: studentVerificationPDA,
@@ -453,7 +731,7 @@ describe("user verification", () => {
     const [electionPDA] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from(electionId),
       ],
       program.programId
@@ -465,19 +743,40 @@ describe("user verification", () => {
         electionId,
         "Multi-Type Election",
         candidatePublicKeys,
-        2,
-        3, // num_plus_votes
-        1, // num_minus_votes
-        [{ student: {} }, { staff: {} }] // Allow both types
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT, STAFF], // Allow both types
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify and register a student
     const studentUser = Keypair.generate();
 
@@ -489,19 +788,15 @@ describe("user verification", () => {
     await confirmTx(studentAirdrop); // Make sure to wait for confirmation
 
     const [studentVerificationPDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_verification"), studentUser.publicKey.toBuffer()],
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        studentUser.publicKey.toBuffer(),
+      ],
       program.programId
     );
 
-    await program.methods
-      .verifyUser("170404054", { student: {} })
-      .accounts({
-        user: studentUser.publicKey,
-        userVerification: studentVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([studentUser])
-      .rpc();
+    await verifyUser(program, authority, studentUser, "170404054", STUDENT);
 
     const [studentVoterPDA] = PublicKey.findProgramAddressSync(
       [
@@ -514,9 +809,11 @@ describe("user verification", () => {
 
     // Student should be able to register
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: studentUser.publicKey,
+        payer: studentUser.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: studentVoterPDA,
         userVerification: studentVerificationPDA,
@@ -536,19 +833,15 @@ describe("user verification", () => {
     await confirmTx(staffAirdrop); // Make sure to wait for confirmation
 
     const [staffVerificationPDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_verification"), staffUser.publicKey.toBuffer()],
+      [
+        Buffer.from("user_verification"),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
+        staffUser.publicKey.toBuffer(),
+      ],
       program.programId
     );
 
-    await program.methods
-      .verifyUser("170404055", { staff: {} })
-      .accounts({
-        user: staffUser.publicKey,
-        userVerification: staffVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([staffUser])
-      .rpc();
+    await verifyUser(program, authority, staffUser, "170404055", STAFF);
 
     const [staffVoterPDA] = PublicKey.findProgramAddressSync(
       [
@@ -561,9 +854,11 @@ describe("user verification", () => {
 
     // Staff should also be able to register
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: staffUser.publicKey,
+        payer: staffUser.publicKey,
+        sponsorship: null,
         election: electionPDA,
         electionVoter: staffVoterPDA,
         userVerification: staffVerificationPDA,
@@ -581,4 +876,71 @@ describe("user verification", () => {
     expect(studentVoter.isEligible).to.be.true;
     expect(staffVoter.isEligible).to.be.true;
   });
+
+  it("Verifies users in categories added by organisation admins", async () => {
+    const categoriesPDA = getCategoriesPDA(program, organizationPDA);
+    const registry = await program.account.categoryRegistry.fetch(
+      categoriesPDA
+    );
+    const alumni = registry.categories.length;
+
+    await program.methods
+      .addCategory("Alumni")
+      .accounts({
+        admin: authority.publicKey,
+        organization: organizationPDA,
+        categoryRegistry: categoriesPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    await verifyUser(program, authority, user, "170404021", alumni);
+
+    const verificationAccount = await program.account.userVerification.fetch(
+      userVerificationPDA
+    );
+    expect(verificationAccount.userType).to.equal(alumni);
+  });
+
+  it("Rejects requests for unknown or retired categories", async () => {
+    const categoriesPDA = getCategoriesPDA(program, organizationPDA);
+    const registry = await program.account.categoryRegistry.fetch(
+      categoriesPDA
+    );
+
+    try {
+      await verifyUser(program, authority, user, "170404021", registry.categories.length);
+
+      expect.fail("Should have thrown UnknownCategory error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnknownCategory");
+    }
+
+    await program.methods
+      .addCategory("Visiting")
+      .accounts({
+        admin: authority.publicKey,
+        organization: organizationPDA,
+        categoryRegistry: categoriesPDA,
+      })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .retireCategory(registry.categories.length)
+      .accounts({
+        admin: authority.publicKey,
+        organization: organizationPDA,
+        categoryRegistry: categoriesPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    try {
+      await verifyUser(program, authority, user, "170404021", registry.categories.length);
+
+      expect.fail("Should have thrown UnknownCategory error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnknownCategory");
+    }
+  });
 });
@@ -0,0 +1,288 @@
+// tests/utils.ts
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+
+// Each election authority gets its own organisation, named after its key, in
+// which it is the admin and the provider wallet is the registrar. The provider
+// wallet deploys the program, so it is also the upgrade authority that
+// creates organisations.
+export const organizationId = (admin: PublicKey) => admin.toBase58().slice(0, 32);
+
+export const getOrganizationPDA = (
+  program: Program<Vote>,
+  admin: PublicKey
+): PublicKey => {
+  const [organizationPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("organization"), Buffer.from(organizationId(admin))],
+    program.programId
+  );
+  return organizationPDA;
+};
+
+// Voter categories created by ensureOrganization, in id order
+export const STUDENT = 0;
+export const STAFF = 1;
+
+export const getCategoriesPDA = (
+  program: Program<Vote>,
+  organization: PublicKey
+): PublicKey => {
+  const [categoriesPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("categories"), organization.toBuffer()],
+    program.programId
+  );
+  return categoriesPDA;
+};
+
+export const getVerificationPDA = (
+  program: Program<Vote>,
+  organization: PublicKey,
+  user: PublicKey
+): PublicKey => {
+  const [verificationPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_verification"), organization.toBuffer(), user.toBuffer()],
+    program.programId
+  );
+  return verificationPDA;
+};
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+export const getProgramDataPDA = (program: Program<Vote>): PublicKey => {
+  const [programDataPDA] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE
+  );
+  return programDataPDA;
+};
+
+export const ensureOrganization = async (
+  program: Program<Vote>,
+  admin: Keypair
+) => {
+  const provider = program.provider as anchor.AnchorProvider;
+  const organizationPDA = getOrganizationPDA(program, admin.publicKey);
+
+  const existing = await program.account.organization.fetchNullable(
+    organizationPDA
+  );
+  if (existing) return organizationPDA;
+
+  await program.methods
+    .createOrganization(
+      organizationId(admin.publicKey),
+      "Test University",
+      admin.publicKey
+    )
+    .accounts({
+      upgradeAuthority: provider.wallet.publicKey,
+      program: program.programId,
+      programData: getProgramDataPDA(program),
+      organization: organizationPDA,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  await program.methods
+    .grantRole({ registrar: {} }, provider.wallet.publicKey)
+    .accounts({
+      admin: admin.publicKey,
+      organization: organizationPDA,
+    })
+    .signers([admin])
+    .rpc();
+
+  await program.methods
+    .initializeCategories(["Student", "Staff"])
+    .accounts({
+      admin: admin.publicKey,
+      organization: organizationPDA,
+      categoryRegistry: getCategoriesPDA(program, organizationPDA),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([admin])
+    .rpc();
+
+  return organizationPDA;
+};
+
+export const approveUser = async (
+  program: Program<Vote>,
+  organization: PublicKey,
+  user: PublicKey
+) => {
+  const provider = program.provider as anchor.AnchorProvider;
+  const userVerification = getVerificationPDA(program, organization, user);
+  const { payer } = await program.account.userVerification.fetch(
+    userVerification
+  );
+
+  return program.methods
+    .approveVerification()
+    .accounts({
+      registrar: provider.wallet.publicKey,
+      organization,
+      payer,
+      userVerification,
+    })
+    .rpc();
+};
+
+// Submits a verification request for `user` in `orgAdmin`'s organisation,
+// paid for by the provider wallet, and approves it as registrar
+export const verifyUser = async (
+  program: Program<Vote>,
+  orgAdmin: Keypair,
+  user: Keypair,
+  idNumber: string,
+  userType: number
+) => {
+  const provider = program.provider as anchor.AnchorProvider;
+  const organizationPDA = await ensureOrganization(program, orgAdmin);
+
+  await program.methods
+    .requestVerification(idNumber, userType)
+    .accounts({
+      user: user.publicKey,
+      payer: provider.wallet.publicKey,
+      organization: organizationPDA,
+      userVerification: getVerificationPDA(
+        program,
+        organizationPDA,
+        user.publicKey
+      ),
+      categoryRegistry: getCategoriesPDA(program, organizationPDA),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc();
+
+  return approveUser(program, organizationPDA, user.publicKey);
+};
+
+export const setElectionStatus = async (
+  program: Program<Vote>,
+  authority: Keypair,
+  election: PublicKey,
+  status: any
+) => {
+  return program.methods
+    .updateElectionStatus(status)
+    .accounts({
+      authority: authority.publicKey,
+      election,
+    })
+    .signers([authority])
+    .rpc();
+};
+
+export const confirmTx = async (program: Program<Vote>, signature: string) => {
+  const { connection } = program.provider;
+  const latestBlockhash = await connection.getLatestBlockhash();
+  await connection.confirmTransaction({ signature, ...latestBlockhash });
+};
+
+// Airdrops `sol` to each wallet in turn
+export const fund = async (
+  program: Program<Vote>,
+  wallets: Keypair[],
+  sol = 100
+) => {
+  for (const wallet of wallets) {
+    await confirmTx(
+      program,
+      await program.provider.connection.requestAirdrop(
+        wallet.publicKey,
+        sol * LAMPORTS_PER_SOL
+      )
+    );
+  }
+};
+
+export const getElectionPDA = (
+  program: Program<Vote>,
+  authority: PublicKey,
+  electionId: string
+): PublicKey => {
+  const [electionPDA] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("election"),
+      getOrganizationPDA(program, authority).toBuffer(),
+      Buffer.from(electionId),
+    ],
+    program.programId
+  );
+  return electionPDA;
+};
+
+// Accounts seeded by an election and a wallet: "election_voter", "ballot", ...
+export const getElectionAccountPDA = (
+  program: Program<Vote>,
+  seed: string,
+  election: PublicKey,
+  key: PublicKey
+): PublicKey => {
+  const [accountPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), election.toBuffer(), key.toBuffer()],
+    program.programId
+  );
+  return accountPDA;
+};
+
+// A single-winner plurality election open to students, with every optional
+// feature switched off; tests override only the fields they exercise
+export const defaultElectionConfig = () => ({
+  numWinners: 1,
+  numPlusVotes: 1,
+  numMinusVotes: 0,
+  allowedVoterTypes: [STUDENT],
+  startTime: null,
+  endTime: null,
+  allowRevote: false,
+  secretBallot: false,
+  votingMethod: { plurality: {} } as any,
+  creditBudget: 0,
+  voteMint: null,
+  nftCollection: null,
+  oneVotePerNft: false,
+  voterMerkleRoot: null,
+});
+
+// Initialises `electionId` in the authority's organisation, creating the
+// organisation first if needed, and returns the election's address
+export const createElection = async (
+  program: Program<Vote>,
+  authority: Keypair,
+  electionId: string,
+  candidates: PublicKey[],
+  overrides: { [field: string]: any } = {}
+) => {
+  const organizationPDA = await ensureOrganization(program, authority);
+  const electionPDA = getElectionPDA(program, authority.publicKey, electionId);
+
+  await program.methods
+    .initialize(electionId, "Test Election", candidates, {
+      ...defaultElectionConfig(),
+      ...overrides,
+    })
+    .accounts({
+      authority: authority.publicKey,
+      organization: organizationPDA,
+      election: electionPDA,
+      categoryRegistry: getCategoriesPDA(program, organizationPDA),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc();
+
+  return electionPDA;
+};
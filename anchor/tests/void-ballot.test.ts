@@ -0,0 +1,287 @@
+// tests/void-ballot.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getOrganizationPDA,
+  getVerificationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("ballot voiding", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets; the third voter delegates to the second
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "appeal-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const verificationPDA = (key: PublicKey) =>
+    getVerificationPDA(
+      program,
+      getOrganizationPDA(program, authority.publicKey),
+      key
+    );
+
+  const castVote = (voter: Keypair, plusVotes: number[]) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        electionVoter: pda("election_voter", voter.publicKey),
+        userVerification: verificationPDA(voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  const setVoterStatus = (voter: Keypair, status: any, reason: any) =>
+    program.methods
+      .updateVoterStatus(status, reason)
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+  const voidBallot = (voter: Keypair, reason: any, delegators: Keypair[] = []) =>
+    program.methods
+      .voidBallot(reason)
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voter.publicKey),
+        ballot: pda("ballot", voter.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        delegators.map((delegator) => ({
+          pubkey: pda("election_voter", delegator.publicKey),
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([authority])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...voters]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    for (const [i, voter] of voters.entries()) {
+      await verifyUser(program, authority, voter, `17080802${i}`, STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: verificationPDA(voter.publicKey),
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+    await program.methods
+      .delegateVote()
+      .accounts({
+        voter: voters[2].publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[2].publicKey),
+        delegateVoter: pda("election_voter", voters[1].publicKey),
+      })
+      .signers([voters[2]])
+      .rpc();
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    await castVote(voters[0], [0]);
+    await program.methods
+      .vote(Buffer.from([1]), Buffer.from([]))
+      .accounts({
+        voter: voters[1].publicKey,
+        payer: voters[1].publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voters[1].publicKey),
+        electionVoter: pda("election_voter", voters[1].publicKey),
+        userVerification: verificationPDA(voters[1].publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        {
+          pubkey: pda("election_voter", voters[2].publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([voters[1]])
+      .rpc();
+  });
+
+  it("Should withdraw a revoked voter's ballot with a traceable record", async () => {
+    await setVoterStatus(voters[0], { revoked: {} }, { fraud: {} });
+    await voidBallot(voters[0], { fraud: {} });
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("0");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("2");
+    expect(election.totalVoters).to.equal(1);
+    expect(election.totalWeight.toString()).to.equal("2");
+
+    const ballot = await program.account.ballot.fetch(
+      pda("ballot", voters[0].publicKey)
+    );
+    expect(ballot.voided).to.be.true;
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voters[0].publicKey)
+    );
+    expect(electionVoter.isEligible).to.be.false;
+    expect(electionVoter.statusHistory.length).to.equal(2);
+    expect(electionVoter.statusHistory[0].to).to.deep.equal({ revoked: {} });
+    expect(electionVoter.statusHistory[0].ballotVoided).to.be.false;
+    expect(electionVoter.statusHistory[1].ballotVoided).to.be.true;
+    expect(electionVoter.statusHistory[1].reason).to.deep.equal({ fraud: {} });
+    expect(electionVoter.statusHistory[1].changedBy.toString()).to.equal(
+      authority.publicKey.toString()
+    );
+
+    try {
+      await voidBallot(voters[0], { fraud: {} });
+
+      expect.fail("Should have thrown BallotVoided error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotVoided");
+    }
+  });
+
+  it("Should grow the status history instead of dropping entries", async () => {
+    // Nine changes, ending suspended, outgrow the eight entries allocated up front
+    for (let i = 0; i < 9; i++) {
+      const status = i % 2 === 0 ? { suspended: {} } : { active: {} };
+      await setVoterStatus(voters[0], status, { administrative: {} });
+    }
+    await voidBallot(voters[0], { misconduct: {} });
+
+    const ballot = await program.account.ballot.fetch(
+      pda("ballot", voters[0].publicKey)
+    );
+    expect(ballot.voided).to.be.true;
+
+    // Every change is kept, the first one included
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voters[0].publicKey)
+    );
+    expect(electionVoter.statusHistory.length).to.equal(10);
+    expect(electionVoter.statusHistory[0].from).to.deep.equal({ active: {} });
+    expect(electionVoter.statusHistory[0].to).to.deep.equal({ suspended: {} });
+    expect(electionVoter.statusHistory[9].ballotVoided).to.be.true;
+    expect(electionVoter.statusHistory[9].reason).to.deep.equal({
+      misconduct: {},
+    });
+
+    const account = await provider.connection.getAccountInfo(
+      pda("election_voter", voters[0].publicKey)
+    );
+    expect(account.data.length).to.be.greaterThan(
+      program.account.electionVoter.size
+    );
+  });
+
+  it("Should record the void on the voters who delegated to the ballot", async () => {
+    await setVoterStatus(voters[1], { revoked: {} }, { fraud: {} });
+
+    try {
+      await voidBallot(voters[1], { fraud: {} });
+
+      expect.fail("Should have thrown DelegatorsMissing error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DelegatorsMissing");
+    }
+
+    await voidBallot(voters[1], { fraud: {} }, [voters[2]]);
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[1].plusVotes.toString()).to.equal("0");
+    expect(election.totalWeight.toString()).to.equal("1");
+
+    // The delegator stays voted, with the void on their own record
+    const delegator = await program.account.electionVoter.fetch(
+      pda("election_voter", voters[2].publicKey)
+    );
+    expect(delegator.hasVoted).to.be.true;
+    expect(delegator.votedThrough.toString()).to.equal(
+      voters[1].publicKey.toString()
+    );
+    expect(delegator.statusHistory.length).to.equal(1);
+    expect(delegator.statusHistory[0].from).to.deep.equal({ active: {} });
+    expect(delegator.statusHistory[0].to).to.deep.equal({ active: {} });
+    expect(delegator.statusHistory[0].ballotVoided).to.be.true;
+    expect(delegator.statusHistory[0].reason).to.deep.equal({ fraud: {} });
+    expect(delegator.statusHistory[0].changedBy.toString()).to.equal(
+      authority.publicKey.toString()
+    );
+  });
+
+  it("Should keep the ballots of active and on-hold voters", async () => {
+    try {
+      await voidBallot(voters[1], { misconduct: {} });
+
+      expect.fail("Should have thrown BallotVoidNotAllowed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotVoidNotAllowed");
+    }
+
+    await setVoterStatus(voters[1], { onHold: {} }, { misconduct: {} });
+
+    try {
+      await voidBallot(voters[1], { misconduct: {} });
+
+      expect.fail("Should have thrown BallotVoidNotAllowed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BallotVoidNotAllowed");
+    }
+  });
+});
@@ -10,6 +10,15 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Vote } from "../target/types/vote";
 import { expect } from "chai";
+import {
+  STAFF,
+  STUDENT,
+  ensureOrganization,
+  getCategoriesPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
 
 describe("vote", () => {
   const provider = anchor.AnchorProvider.env();
@@ -66,14 +75,18 @@ describe("vote", () => {
       [electionPDA, electionBump] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("election"),
-          authority.publicKey.toBuffer(),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
           Buffer.from(electionId),
         ],
         program.programId
       );
 
       [voterVerificationPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_verification"), voter.publicKey.toBuffer()],
+        [
+          Buffer.from("user_verification"),
+          getOrganizationPDA(program, authority.publicKey).toBuffer(),
+          voter.publicKey.toBuffer(),
+        ],
         program.programId
       );
 
@@ -110,13 +123,30 @@ describe("vote", () => {
           electionId,
           electionName,
           candidateKeys,
-          2, // two winners
-          3, // 3 plus votes allowed
-          1, // 1 minus vote allowed
-          [{ student: {} }, { staff: {} }] // Allow both student and staff voters
+          {
+            numWinners: 2, // two winners
+            numPlusVotes: 3, // 3 plus votes allowed
+            numMinusVotes: 1, // 1 minus vote allowed
+            allowedVoterTypes: [STUDENT, STAFF], // Allow both student and staff voters
+            startTime: null,
+            endTime: null,
+            allowRevote: false,
+            secretBallot: false,
+            votingMethod: { d21: {} },
+            creditBudget: 0,
+            voteMint: null,
+            nftCollection: null,
+            oneVotePerNft: false,
+            voterMerkleRoot: null,
+          }
         )
         .accounts({
           authority: authority.publicKey,
+          organization: await ensureOrganization(program, authority),
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
           election: electionPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -136,7 +166,7 @@ describe("vote", () => {
       expect(election.numPlusVotes).to.equal(3);
       expect(election.numMinusVotes).to.equal(1);
       expect(election.allowedVoterTypes).to.have.lengthOf(2);
-      expect(election.status).to.deep.equal({ active: {} });
+      expect(election.status).to.deep.equal({ created: {} });
       expect(election.totalVoters).to.equal(0);
       expect(election.candidates.length).to.equal(5);
       expect(election.winners.length).to.equal(0);
@@ -167,39 +197,52 @@ describe("vote", () => {
         electionId,
         electionName,
         candidateKeys,
-        2, // two winners
-        3, // 3 plus votes allowed
-        1, // 1 minus vote allowed
-        [{ student: {} }] // Only allow student voters for this test
+        {
+          numWinners: 2, // two winners
+          numPlusVotes: 3, // 3 plus votes allowed
+          numMinusVotes: 1, // 1 minus vote allowed
+          allowedVoterTypes: [STUDENT], // Only allow student voters for this test
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     await confirmTx(initTx);
 
     // First verify the voter as a student
-    const verifyTx = await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
-
-    await confirmTx(verifyTx);
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Then register voter for the election
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -211,11 +254,14 @@ describe("vote", () => {
     // Cast votes
     const plusVotes = Buffer.from([0, 1, 2]); // 3 plus votes
     const minusVotes = Buffer.from([4]); // 1 minus vote
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
     const voteTx = await program.methods
       .vote(plusVotes, minusVotes)
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: ballotPDA,
         electionVoter: electionVoterPDA,
@@ -272,13 +318,30 @@ describe("vote", () => {
           electionId,
           electionName,
           candidateKeys,
-          1, // one winner
-          2, // 2 plus votes
-          3, // 3 minus votes (invalid: more minus than plus votes)
-          [{ student: {} }] // Allow student voters
+          {
+            numWinners: 1, // one winner
+            numPlusVotes: 2, // 2 plus votes
+            numMinusVotes: 3, // 3 minus votes (invalid: more minus than plus votes)
+            allowedVoterTypes: [STUDENT], // Allow student voters
+            startTime: null,
+            endTime: null,
+            allowRevote: false,
+            secretBallot: false,
+            votingMethod: { d21: {} },
+            creditBudget: 0,
+            voteMint: null,
+            nftCollection: null,
+            oneVotePerNft: false,
+            voterMerkleRoot: null,
+          }
         )
         .accounts({
           authority: authority.publicKey,
+          organization: await ensureOrganization(program, authority),
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
           election: electionPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -297,23 +360,52 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Try to register without verification
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           userVerification: voterVerificationPDA,
           electionVoter: electionVoterPDA,
@@ -338,36 +430,51 @@ describe("vote", () => {
         electionId,
         electionName,
         candidateKeys,
-        2,
-        3,
-        1,
-        [{ student: {} }] // Only students allowed
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT], // Only students allowed
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as staff (wrong type)
-    await program.methods
-      .verifyUser("170404021", { staff: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STAFF);
 
     // Try to register with wrong voter type
     try {
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           userVerification: voterVerificationPDA,
           electionVoter: electionVoterPDA,
@@ -388,33 +495,54 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -428,10 +556,14 @@ describe("vote", () => {
     const tooManyMinusVotes = Buffer.from([3, 4]); // 2 minus votes when only 1 is allowed
 
     try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
       await program.methods
         .vote(plusVotes, tooManyMinusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -453,33 +585,54 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -493,10 +646,14 @@ describe("vote", () => {
     const minusVotes = Buffer.from([4]);
 
     try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
       await program.methods
         .vote(duplicatePlusVotes, minusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -518,33 +675,54 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -558,10 +736,14 @@ describe("vote", () => {
     const minusVotes = Buffer.from([1]); // 1 appears in both plus and minus
 
     try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
       await program.methods
         .vote(plusVotes, minusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -583,33 +765,54 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -623,10 +826,14 @@ describe("vote", () => {
     const minusVotes = Buffer.from([4]);
 
     try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
       await program.methods
         .vote(plusVotes, minusVotes)
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           ballot: ballotPDA,
           electionVoter: electionVoterPDA,
@@ -649,7 +856,7 @@ describe("vote", () => {
     const [electionPDA1] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from("election-1"),
       ],
       program.programId
@@ -658,7 +865,7 @@ describe("vote", () => {
     const [electionPDA2] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("election"),
-        authority.publicKey.toBuffer(),
+        getOrganizationPDA(program, authority.publicKey).toBuffer(),
         Buffer.from("election-2"),
       ],
       program.programId
@@ -670,46 +877,88 @@ describe("vote", () => {
         "election-1",
         "First Election",
         candidateKeys,
-        2,
-        3,
-        1,
-        [{ student: {} }] // Only students can vote
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT], // Only students can vote
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA1,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA1, {
+      registration: {},
+    });
+
     // Initialize second election
     await program.methods
       .initialize(
         "election-2",
         "Second Election",
         candidateKeys,
-        2,
-        3,
-        1,
-        [{ staff: {} }] // Only staff can vote
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STAFF], // Only staff can vote
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA2,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA2, {
+      registration: {},
+    });
+
     // Verify both elections exist and are different
     const election1 = await program.account.election.fetch(electionPDA1);
     const election2 = await program.account.election.fetch(electionPDA2);
 
     expect(election1.name).to.equal("First Election");
     expect(election2.name).to.equal("Second Election");
-    expect(election1.allowedVoterTypes[0]).to.deep.equal({ student: {} });
-    expect(election2.allowedVoterTypes[0]).to.deep.equal({ staff: {} });
+    expect(election1.allowedVoterTypes[0]).to.deep.equal(STUDENT);
+    expect(election2.allowedVoterTypes[0]).to.deep.equal(STAFF);
   });
 
   it("Should successfully end election", async () => {
@@ -718,33 +967,54 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
 
     // Register voter
     await program.methods
-      .registerVoter()
+      .registerVoter([])
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         userVerification: voterVerificationPDA,
         electionVoter: electionVoterPDA,
@@ -756,11 +1026,14 @@ describe("vote", () => {
     // Cast some votes
     const plusVotes = Buffer.from([0, 1, 2]); // Vote for first three candidates
     const minusVotes = Buffer.from([4]); // Minus vote for last candidate
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
     await program.methods
       .vote(plusVotes, minusVotes)
       .accounts({
         voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
         election: electionPDA,
         ballot: ballotPDA,
         electionVoter: electionVoterPDA,
@@ -834,19 +1107,48 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Try to end election with wrong signer (voter instead of authority)
     try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
       await program.methods
         .end()
         .accounts({
@@ -868,17 +1170,46 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
     // End election first time
     await program.methods
       .end()
@@ -912,27 +1243,48 @@ describe("vote", () => {
     const candidateKeys = candidates.map((c) => c.publicKey);
 
     await program.methods
-      .initialize(electionId, electionName, candidateKeys, 2, 3, 1, [
-        { student: {} },
-      ])
+      .initialize(
+        electionId,
+        electionName,
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as student
-    await program.methods
-      .verifyUser("170404021", { student: {} })
-      .accounts({
-        user: voter.publicKey,
-        userVerification: voterVerificationPDA,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([voter])
-      .rpc();
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
 
     // End election
     await program.methods
@@ -948,9 +1300,11 @@ describe("vote", () => {
     try {
       // Try to register after election ended
       await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
           userVerification: voterVerificationPDA,
           electionVoter: electionVoterPDA,
@@ -959,9 +1313,9 @@ describe("vote", () => {
         .signers([voter])
         .rpc();
 
-      expect.fail("Should have thrown ElectionNotActive error");
+      expect.fail("Should have thrown RegistrationClosed error");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("ElectionNotActive");
+      expect(err.error.errorCode.code).to.equal("RegistrationClosed");
     }
   });
 
@@ -975,47 +1329,573 @@ describe("vote", () => {
         electionId,
         electionName,
         candidateKeys,
-        2,
-        3,
-        1,
-        [{ student: {} }] // Only students allowed
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT], // Only students allowed
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
       )
       .accounts({
         authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
         election: electionPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
       .rpc();
 
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
     // Verify voter as staff (wrong type)
+    await verifyUser(program, authority, voter, "170404021", STAFF);
+
+    try {
+      // Try to register with wrong voter type
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: voterVerificationPDA,
+          electionVoter: electionVoterPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown UserTypeNotAllowed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UserTypeNotAllowed");
+    }
+  });
+
+  it("Should keep a scheduled election in Created until its start time", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+    const now = Math.floor(Date.now() / 1000);
+
     await program.methods
-      .verifyUser("170404021", { staff: {} })
+      .initialize(
+        electionId,
+        "Scheduled Election",
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: new anchor.BN(now + 3600),
+          endTime: new anchor.BN(now + 7200),
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
       .accounts({
-        user: voter.publicKey,
+        authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ registration: {} });
+    expect(election.startTime.toNumber()).to.equal(now + 3600);
+    expect(election.endTime.toNumber()).to.equal(now + 7200);
+
+    // Registration is open before voting starts
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
         userVerification: voterVerificationPDA,
+        electionVoter: electionVoterPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([voter])
       .rpc();
 
+    // The authority cannot open voting ahead of the schedule
+    try {
+      await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+      expect.fail("Should have thrown ElectionNotStarted error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ElectionNotStarted");
+    }
+
     try {
-      // Try to register with wrong voter type
       await program.methods
-        .registerVoter()
+        .vote(Buffer.from([0, 1]), Buffer.from([]))
         .accounts({
           voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
           election: electionPDA,
-          userVerification: voterVerificationPDA,
+          ballot: ballotPDA,
           electionVoter: electionVoterPDA,
+          userVerification: voterVerificationPDA,
           systemProgram: SystemProgram.programId,
         })
         .signers([voter])
         .rpc();
 
-      expect.fail("Should have thrown UserTypeNotAllowed error");
+      expect.fail("Should have thrown VotingNotOpen error");
     } catch (err) {
-      expect(err.error.errorCode.code).to.equal("UserTypeNotAllowed");
+      expect(err.error.errorCode.code).to.equal("VotingNotOpen");
+    }
+  });
+
+  it("Should reject an end time before the start time", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await program.methods
+        .initialize(
+          electionId,
+          "Backwards Election",
+          candidateKeys,
+          {
+            numWinners: 2,
+            numPlusVotes: 3,
+            numMinusVotes: 1,
+            allowedVoterTypes: [STUDENT],
+            startTime: new anchor.BN(now + 7200),
+            endTime: new anchor.BN(now + 3600),
+            allowRevote: false,
+            secretBallot: false,
+            votingMethod: { d21: {} },
+            creditBudget: 0,
+            voteMint: null,
+            nftCollection: null,
+            oneVotePerNft: false,
+            voterMerkleRoot: null,
+          }
+        )
+        .accounts({
+          authority: authority.publicKey,
+          organization: await ensureOrganization(program, authority),
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
+          election: electionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidElectionTimes error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidElectionTimes");
+    }
+  });
+
+  it("Should let anyone finalize an election once its end time passes", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+    const now = Math.floor(Date.now() / 1000);
+
+    await program.methods
+      .initialize(
+        electionId,
+        "Short Election",
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: new anchor.BN(now + 3),
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    // Too early to finalize
+    try {
+      await program.methods
+        .finalize()
+        .accounts({ election: electionPDA })
+        .rpc();
+
+      expect.fail("Should have thrown ElectionStillRunning error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ElectionStillRunning");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    // Any wallet can crank the election once the end time has passed
+    await program.methods.finalize().accounts({ election: electionPDA }).rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ ended: {} });
+    expect(election.endTime.toNumber()).to.equal(now + 3);
+    expect(election.winners.length).to.equal(2);
+  });
+
+  it("Should prevent finalizing an election without an end time", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+
+    await program.methods
+      .initialize(
+        electionId,
+        "Open Election",
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    try {
+      await program.methods.finalize().accounts({ election: electionPDA }).rpc();
+
+      expect.fail("Should have thrown EndTimeNotSet error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EndTimeNotSet");
+    }
+  });
+
+  it("Should cancel an election and let everyone reclaim rent", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+    // Never created here, so all rent goes back to the voter
+    const [sponsorshipPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsorship"), electionPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(
+        electionId,
+        "Typo Election",
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+
+    await verifyUser(program, authority, voter, "170404021", STUDENT);
+    await program.methods
+      .registerVoter([])
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        userVerification: voterVerificationPDA,
+        electionVoter: electionVoterPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    await program.methods
+      .vote(Buffer.from([0, 1]), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: ballotPDA,
+        electionVoter: electionVoterPDA,
+        userVerification: voterVerificationPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+    // Voters cannot reclaim rent while the election is still running
+    try {
+      await program.methods
+        .closeBallot()
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          election: electionPDA,
+          ballot: ballotPDA,
+          sponsorship: sponsorshipPDA,
+        })
+        .signers([voter])
+        .rpc();
+
+      expect.fail("Should have thrown ElectionNotFinal error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ElectionNotFinal");
+    }
+
+    await program.methods
+      .cancel("Wrong candidate key")
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.status).to.deep.equal({ cancelled: {} });
+    expect(election.winners.length).to.equal(0);
+
+    // The authority may close the election before voters close their records
+    await program.methods
+      .closeElection()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        sponsorship: sponsorshipPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .closeBallot()
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        election: electionPDA,
+        ballot: ballotPDA,
+        sponsorship: sponsorshipPDA,
+      })
+      .signers([voter])
+      .rpc();
+
+    await program.methods
+      .closeElectionVoter()
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        election: electionPDA,
+        electionVoter: electionVoterPDA,
+        sponsorship: sponsorshipPDA,
+      })
+      .signers([voter])
+      .rpc();
+
+    expect(await program.account.ballot.fetchNullable(ballotPDA)).to.be.null;
+    expect(
+      await program.account.electionVoter.fetchNullable(electionVoterPDA)
+    ).to.be.null;
+
+    // A tombstone keeps the id taken so stale records can't join a new election
+    const tombstone = await program.account.electionTombstone.fetch(electionPDA);
+    expect(tombstone.id).to.equal(electionId);
+    expect(tombstone.authority.toString()).to.equal(
+      authority.publicKey.toString()
+    );
+
+    try {
+      await program.methods
+        .initialize(electionId, "Typo Election", candidateKeys, {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          organization: await ensureOrganization(program, authority),
+          categoryRegistry: getCategoriesPDA(
+            program,
+            getOrganizationPDA(program, authority.publicKey)
+          ),
+          election: electionPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should not re-initialise a closed election");
+    } catch (err) {
+      expect(err.toString()).to.include("already in use");
+    }
+  });
+
+  it("Should prevent closing an election that is still running", async () => {
+    const candidateKeys = candidates.map((c) => c.publicKey);
+
+    await program.methods
+      .initialize(
+        electionId,
+        "Running Election",
+        candidateKeys,
+        {
+          numWinners: 2,
+          numPlusVotes: 3,
+          numMinusVotes: 1,
+          allowedVoterTypes: [STUDENT],
+          startTime: null,
+          endTime: null,
+          allowRevote: false,
+          secretBallot: false,
+          votingMethod: { d21: {} },
+          creditBudget: 0,
+          voteMint: null,
+          nftCollection: null,
+          oneVotePerNft: false,
+          voterMerkleRoot: null,
+        }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        organization: await ensureOrganization(program, authority),
+        categoryRegistry: getCategoriesPDA(
+          program,
+          getOrganizationPDA(program, authority.publicKey)
+        ),
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    try {
+      await program.methods
+        .closeElection()
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          sponsorship: PublicKey.findProgramAddressSync(
+            [Buffer.from("sponsorship"), electionPDA.toBuffer()],
+            program.programId
+          )[0],
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown ElectionNotFinal error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ElectionNotFinal");
     }
   });
 });
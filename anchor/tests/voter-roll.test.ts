@@ -0,0 +1,185 @@
+// tests/voter-roll.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  setElectionStatus,
+} from "./utils";
+
+describe("voter roll", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "roll-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const addToRoll = (signer: Keypair, roll: Keypair[]) =>
+    program.methods
+      .addToVoterRoll(roll.map((v) => v.publicKey))
+      .accounts({
+        authority: signer.publicKey,
+        election: electionPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        roll.map((v) => ({
+          pubkey: pda("election_voter", v.publicKey),
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([signer])
+      .rpc();
+
+  const castVote = (voter: Keypair, plusVotes: number[]) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from([]))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        electionVoter: pda("election_voter", voter.publicKey),
+        userVerification: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority]);
+
+    electionPDA = await createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey)
+    );
+
+    // Rolls can only be imported while registration is open
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+  });
+
+  it("Should put voters on the roll as Pending at the authority's expense", async () => {
+    await addToRoll(authority, voters);
+
+    for (const voter of voters) {
+      const electionVoter = await program.account.electionVoter.fetch(
+        pda("election_voter", voter.publicKey)
+      );
+      expect(electionVoter.status).to.deep.equal({ pending: {} });
+      expect(electionVoter.registeredBy.toString()).to.equal(
+        authority.publicKey.toString()
+      );
+
+      // The voters never held any SOL
+      expect(await provider.connection.getBalance(voter.publicKey)).to.equal(0);
+    }
+
+    // Importing the same roll again leaves existing entries alone
+    await addToRoll(authority, voters);
+  });
+
+  it("Should only let roll voters vote once activated", async () => {
+    await addToRoll(authority, voters);
+
+    await program.methods
+      .updateVoterStatus({ active: {} }, { verified: {} })
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+        electionVoter: pda("election_voter", voters[0].publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    // Voters pay for their own ballot account
+    await fund(program, voters.slice(0, 2), 1);
+
+    await castVote(voters[0], [0]);
+
+    try {
+      await castVote(voters[1], [0]);
+
+      expect.fail("Should have thrown VoterNotEligible error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("VoterNotEligible");
+    }
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.totalVoters).to.equal(1);
+  });
+
+  it("Should keep pending voters off the frozen roll once voting opens", async () => {
+    await addToRoll(authority, voters);
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+
+    try {
+      await program.methods
+        .updateVoterStatus({ active: {} }, { verified: {} })
+        .accounts({
+          authority: authority.publicKey,
+          election: electionPDA,
+          electionVoter: pda("election_voter", voters[0].publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect.fail("Should have thrown RegistrationClosed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RegistrationClosed");
+    }
+
+    // Nor can new voters be imported
+    try {
+      await addToRoll(authority, [Keypair.generate()]);
+
+      expect.fail("Should have thrown RegistrationClosed error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RegistrationClosed");
+    }
+  });
+
+  it("Should reject roll imports from anyone else", async () => {
+    const outsider = Keypair.generate();
+    await fund(program, [outsider], 10);
+
+    try {
+      await addToRoll(outsider, voters);
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+});
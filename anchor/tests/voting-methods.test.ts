@@ -0,0 +1,282 @@
+// tests/voting-methods.test.ts
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Vote } from "../target/types/vote";
+import { expect } from "chai";
+import {
+  STUDENT,
+  createElection,
+  fund,
+  getElectionAccountPDA,
+  getElectionPDA,
+  getOrganizationPDA,
+  setElectionStatus,
+  verifyUser,
+} from "./utils";
+
+describe("voting methods", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Vote as Program<Vote>;
+
+  // Test wallets
+  let authority: Keypair;
+  let voters: Keypair[];
+  let candidates: Keypair[];
+
+  const electionId = "methods-2024-01";
+  let electionPDA: PublicKey;
+
+  const pda = (seed: string, key: PublicKey) =>
+    getElectionAccountPDA(program, seed, electionPDA, key);
+
+  const initialize = (
+    votingMethod: any,
+    numPlusVotes: number,
+    numMinusVotes: number,
+    creditBudget = 0
+  ) =>
+    createElection(
+      program,
+      authority,
+      electionId,
+      candidates.map((c) => c.publicKey),
+      { votingMethod, numPlusVotes, numMinusVotes, creditBudget }
+    );
+
+  // Verifies and registers every voter, then opens voting
+  const openVoting = async () => {
+    await setElectionStatus(program, authority, electionPDA, {
+      registration: {},
+    });
+    for (const [i, voter] of voters.entries()) {
+      await verifyUser(program, authority, voter, `17040402${i}`, STUDENT);
+      await program.methods
+        .registerVoter([])
+        .accounts({
+          voter: voter.publicKey,
+          payer: voter.publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          userVerification: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("user_verification"),
+              getOrganizationPDA(program, authority.publicKey).toBuffer(),
+              voter.publicKey.toBuffer(),
+            ],
+            program.programId
+          )[0],
+          electionVoter: pda("election_voter", voter.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voter])
+        .rpc();
+    }
+    await setElectionStatus(program, authority, electionPDA, { voting: {} });
+  };
+
+  const castVote = (voter: Keypair, plusVotes: number[], minusVotes: number[]) =>
+    program.methods
+      .vote(Buffer.from(plusVotes), Buffer.from(minusVotes))
+      .accounts({
+        voter: voter.publicKey,
+        payer: voter.publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voter.publicKey),
+        electionVoter: pda("election_voter", voter.publicKey),
+        userVerification: PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("user_verification"),
+            getOrganizationPDA(program, authority.publicKey).toBuffer(),
+            voter.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+
+  beforeEach(async () => {
+    authority = Keypair.generate();
+    voters = Array(3)
+      .fill(0)
+      .map(() => Keypair.generate());
+    candidates = Array(4)
+      .fill(0)
+      .map(() => Keypair.generate());
+
+    await fund(program, [authority, ...voters]);
+
+    electionPDA = getElectionPDA(program, authority.publicKey, electionId);
+  });
+
+  it("Should limit plurality ballots to a single choice", async () => {
+    await initialize({ plurality: {} }, 1, 0);
+    await openVoting();
+
+    try {
+      await castVote(voters[0], [0, 1], []);
+
+      expect.fail("Should have thrown TooManyPlusVotes error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TooManyPlusVotes");
+    }
+
+    await castVote(voters[0], [2], []);
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.votingMethod).to.deep.equal({ plurality: {} });
+    expect(election.candidates[2].plusVotes.toString()).to.equal("1");
+  });
+
+  it("Should reject minus votes outside D21", async () => {
+    try {
+      await initialize({ approval: {} }, 4, 1);
+
+      expect.fail("Should have thrown InvalidMinusVoteCount error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidMinusVoteCount");
+    }
+  });
+
+  it("Should count every approved candidate", async () => {
+    await initialize({ approval: {} }, 4, 0);
+    await openVoting();
+
+    await castVote(voters[0], [0, 1, 2], []);
+    await castVote(voters[1], [1], []);
+
+    try {
+      await castVote(voters[2], [], []);
+
+      expect.fail("Should have thrown InvalidBallotForMethod error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBallotForMethod");
+    }
+
+    await program.methods
+      .end()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[1].plusVotes.toString()).to.equal("2");
+    expect(election.winners[0].toString()).to.equal(
+      candidates[1].publicKey.toString()
+    );
+  });
+
+  it("Should award Borda points by preference order", async () => {
+    await initialize({ borda: {} }, 4, 0);
+    await openVoting();
+
+    // With four candidates the ranks are worth 3, 2, 1 and 0 points
+    await castVote(voters[0], [0, 1, 2, 3], []);
+    await castVote(voters[1], [1, 0, 3, 2], []);
+    await castVote(voters[2], [1, 2], []);
+
+    await program.methods
+      .end()
+      .accounts({
+        authority: authority.publicKey,
+        election: electionPDA,
+      })
+      .signers([authority])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("5");
+    expect(election.candidates[1].plusVotes.toString()).to.equal("8");
+    expect(election.candidates[2].plusVotes.toString()).to.equal("3");
+    expect(election.candidates[3].plusVotes.toString()).to.equal("1");
+    expect(election.candidates[1].rank).to.equal(0);
+    expect(election.winners[0].toString()).to.equal(
+      candidates[1].publicKey.toString()
+    );
+  });
+
+  it("Should charge quadratic ballots their squared weights", async () => {
+    await initialize({ quadratic: {} }, 4, 0, 10);
+    await openVoting();
+
+    // 3 votes for one candidate and 1 for another cost 9 + 1 = 10 credits
+    await program.methods
+      .quadraticVote(Buffer.from([0, 2]), [3, 1])
+      .accounts({
+        voter: voters[0].publicKey,
+        payer: voters[0].publicKey,
+        sponsorship: null,
+        election: electionPDA,
+        ballot: pda("ballot", voters[0].publicKey),
+        electionVoter: pda("election_voter", voters[0].publicKey),
+        userVerification: PublicKey.findProgramAddressSync(
+          [Buffer.from("user_verification"), voters[0].publicKey.toBuffer()],
+          program.programId
+        )[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voters[0]])
+      .rpc();
+
+    const election = await program.account.election.fetch(electionPDA);
+    expect(election.candidates[0].plusVotes.toString()).to.equal("3");
+    expect(election.candidates[2].plusVotes.toString()).to.equal("1");
+
+    const electionVoter = await program.account.electionVoter.fetch(
+      pda("election_voter", voters[0].publicKey)
+    );
+    expect(electionVoter.credits).to.equal(0);
+
+    const ballot = await program.account.ballot.fetch(
+      pda("ballot", voters[0].publicKey)
+    );
+    expect(ballot.voteWeights).to.deep.equal([3, 1]);
+  });
+
+  it("Should reject quadratic ballots over the credit budget", async () => {
+    await initialize({ quadratic: {} }, 4, 0, 10);
+    await openVoting();
+
+    try {
+      // 9 + 4 = 13 credits against a budget of 10
+      await program.methods
+        .quadraticVote(Buffer.from([0, 1]), [3, 2])
+        .accounts({
+          voter: voters[0].publicKey,
+          payer: voters[0].publicKey,
+          sponsorship: null,
+          election: electionPDA,
+          ballot: pda("ballot", voters[0].publicKey),
+          electionVoter: pda("election_voter", voters[0].publicKey),
+          userVerification: PublicKey.findProgramAddressSync(
+            [Buffer.from("user_verification"), voters[0].publicKey.toBuffer()],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voters[0]])
+        .rpc();
+
+      expect.fail("Should have thrown InsufficientCredits error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InsufficientCredits");
+    }
+  });
+
+  it("Should require a credit budget for quadratic elections", async () => {
+    try {
+      await initialize({ quadratic: {} }, 4, 0);
+
+      expect.fail("Should have thrown InvalidCreditBudget error");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidCreditBudget");
+    }
+  });
+});
@@ -7,6 +7,7 @@ import { Alert, AlertDescription } from "@/components/ui/alert";
 import { AlertCircle, ArrowLeft, ShieldCheck } from "lucide-react";
 import LoadingSpinner from "@/components/ui/loadingSpinner";
 import { useUserVerification } from "@/hooks/useUserVerification";
+import { STUDENT } from "@/hooks/useProgram";
 
 const AGREEMENTS = [
   {
@@ -33,7 +34,7 @@ const VerificationForm = () => {
   const router = useRouter();
   const { publicKey } = useWallet();
   const {
-    verifyUser,
+    requestVerification,
     isLoading: isVerifying,
     fetchVerification,
   } = useUserVerification();
@@ -69,7 +70,7 @@ const VerificationForm = () => {
 
       setError(null);
       try {
-        await verifyUser("170404021", { student: {} });
+        await requestVerification("170404021", STUDENT);
         router.push("/verify?isVerified=true");
       } catch (err: any) {
         console.error("Verification error:", err);
@@ -88,7 +89,7 @@ const VerificationForm = () => {
         }
       }
     },
-    [publicKey, allAgreementsAccepted, verifyUser, router]
+    [publicKey, allAgreementsAccepted, requestVerification, router]
   );
 
   if (!publicKey) {
@@ -61,6 +61,7 @@ export default function TurnoutAnalysis({ electionPDA }: TurnoutAnalysisProps) {
             const [verificationPDA] = PublicKey.findProgramAddressSync(
               [
                 Buffer.from("user_verification"),
+                election.organization.toBuffer(),
                 voter.account.voter.toBuffer(),
               ],
               program.programId
@@ -1,7 +1,7 @@
 // hooks/useBallot.ts
 import { useCallback, useState } from "react";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { useProgram } from "./useProgram";
+import { ORGANIZATION_PDA, useProgram } from "./useProgram";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { Ballot } from "../types/vote";
 
@@ -33,7 +33,11 @@ export const useBallot = (electionPDA?: PublicKey) => {
       try {
         const ballotPDA = getBallotPDA(electionPDA, publicKey);
         const userVerificationPDA = PublicKey.findProgramAddressSync(
-          [Buffer.from("user_verification"), publicKey.toBuffer()],
+          [
+            Buffer.from("user_verification"),
+            ORGANIZATION_PDA.toBuffer(),
+            publicKey.toBuffer(),
+          ],
           program.programId
         )[0];
         const electionVoterPDA = PublicKey.findProgramAddressSync(
@@ -51,6 +55,8 @@ export const useBallot = (electionPDA?: PublicKey) => {
           .vote(Buffer.from(plusVotes), Buffer.from(minusVotes))
           .accounts({
             voter: publicKey,
+            payer: publicKey,
+            sponsorship: null,
             election: electionPDA,
             ballot: ballotPDA,
             electionVoter: electionVoterPDA,
@@ -3,6 +3,7 @@ import { useRouter } from "next/navigation";
 import { PublicKey } from "@solana/web3.js";
 import { toast } from "sonner";
 import { useElection } from "./useElection";
+import { ORGANIZATION_PDA, STAFF, STUDENT } from "./useProgram";
 import { useWallet } from "@solana/wallet-adapter-react";
 import {
   ElectionFormData,
@@ -199,26 +200,30 @@ export const useCreateElection = () => {
         (candidate) => new PublicKey(candidate.walletAddress)
       );
 
-      // Map voter groups to their corresponding types
+      // Map voter groups to the organisation's category ids
       const allowedVoterTypes = state.data.voterGroups.map((group) =>
-        group === "student" ? { student: {} } : { staff: {} }
+        group === "student" ? STUDENT : STAFF
       );
 
       // Initialize the election
-      await initialize(
-        electionId,
-        state.data.title,
-        candidateKeys,
-        state.data.numWinners,
-        state.data.numPlusVotes,
-        state.data.numMinusVotes,
-        allowedVoterTypes
-      );
-
-      // Get the election PDA
+      await initialize(electionId, state.data.title, candidateKeys, {
+        numWinners: state.data.numWinners,
+        numPlusVotes: state.data.numPlusVotes,
+        numMinusVotes: state.data.numMinusVotes,
+        allowedVoterTypes,
+        startTime: null,
+        endTime: null,
+        allowRevote: false,
+        secretBallot: false,
+        votingMethod: { d21: {} },
+        creditBudget: 0,
+        voteMint: null,
+        nftCollection: null,
+        oneVotePerNft: false,
+        voterMerkleRoot: null,
+      });
 
-      // Then in your try block:
-      const electionPDA = getElectionPDA(publicKey, electionId);
+      const electionPDA = getElectionPDA(ORGANIZATION_PDA, electionId);
 
       if (!electionPDA) {
         throw new Error("Could not generate election PDA");
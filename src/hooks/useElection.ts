@@ -1,8 +1,12 @@
 // hooks/useElection.ts
 import { useCallback, useState } from "react";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { UserType, Election } from "../types/vote";
-import { useProgram } from "./useProgram";
+import { Election, ElectionConfig } from "../types/vote";
+import {
+  CATEGORY_REGISTRY_PDA,
+  ORGANIZATION_PDA,
+  useProgram,
+} from "./useProgram";
 import { useWallet } from "@solana/wallet-adapter-react";
 
 export const useElection = (electionId?: string) => {
@@ -10,12 +14,13 @@ export const useElection = (electionId?: string) => {
   const { publicKey } = useWallet();
   const [isLoading, setIsLoading] = useState(false);
 
+  // Election IDs are unique within an organisation, not per authority
   const getElectionPDA = useCallback(
-    (authority: PublicKey, id: string) => {
+    (organization: PublicKey, id: string) => {
       if (!program) return null;
 
       const [pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("election"), authority.toBuffer(), Buffer.from(id)],
+        [Buffer.from("election"), organization.toBuffer(), Buffer.from(id)],
         program.programId
       );
       return pda;
@@ -28,31 +33,22 @@ export const useElection = (electionId?: string) => {
       id: string,
       name: string,
       candidates: PublicKey[],
-      numWinners: number,
-      numPlusVotes: number,
-      numMinusVotes: number,
-      allowedVoterTypes: UserType[]
+      config: ElectionConfig
     ) => {
       if (!program || !publicKey)
         throw new Error("Program or wallet not connected");
 
       setIsLoading(true);
       try {
-        const electionPDA = getElectionPDA(publicKey, id);
+        const electionPDA = getElectionPDA(ORGANIZATION_PDA, id);
         if (!electionPDA) throw new Error("Could not derive PDA");
         const tx = await program.methods
-          .initialize(
-            id,
-            name,
-            candidates,
-            numWinners,
-            numPlusVotes,
-            numMinusVotes,
-            allowedVoterTypes
-          )
+          .initialize(id, name, candidates, config)
           .accounts({
             authority: publicKey,
+            organization: ORGANIZATION_PDA,
             election: electionPDA,
+            categoryRegistry: CATEGORY_REGISTRY_PDA,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -67,11 +63,11 @@ export const useElection = (electionId?: string) => {
   );
 
   const fetchElectionById = useCallback(
-    async (authority: PublicKey, id: string): Promise<Election | null> => {
+    async (organization: PublicKey, id: string): Promise<Election | null> => {
       if (!program) return null;
 
       try {
-        const pda = getElectionPDA(authority, id);
+        const pda = getElectionPDA(organization, id);
         if (!pda) return null;
 
         const election = await program.account.election.fetch(pda);
@@ -106,7 +102,7 @@ export const useElection = (electionId?: string) => {
 
     setIsLoading(true);
     try {
-      const electionPDA = getElectionPDA(publicKey, electionId);
+      const electionPDA = getElectionPDA(ORGANIZATION_PDA, electionId);
       if (!electionPDA) throw new Error("Could not derive PDA");
 
       const tx = await program.methods
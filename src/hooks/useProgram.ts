@@ -7,6 +7,23 @@ import { Vote } from "../../anchor/target/types/vote";
 import VoteIDL from "../../anchor/target/idl/vote.json";
 
 export const PROGRAM_ID = new PublicKey(VoteIDL.address);
+
+// The organisation this deployment serves; its elections and voter
+// verifications are all derived under it
+export const ORGANIZATION_ID = process.env.NEXT_PUBLIC_ORGANIZATION_ID ?? "";
+export const ORGANIZATION_PDA = PublicKey.findProgramAddressSync(
+  [Buffer.from("organization"), Buffer.from(ORGANIZATION_ID)],
+  PROGRAM_ID
+)[0];
+export const CATEGORY_REGISTRY_PDA = PublicKey.findProgramAddressSync(
+  [Buffer.from("categories"), ORGANIZATION_PDA.toBuffer()],
+  PROGRAM_ID
+)[0];
+
+// Ids of the voter categories the organisation was set up with
+export const STUDENT = 0;
+export const STAFF = 1;
+
 export const useProgram = () => {
   const { wallet, publicKey, signTransaction, signAllTransactions } =
     useWallet();
@@ -1,8 +1,12 @@
 // hooks/useUserVerification.ts
 import { useCallback, useState } from "react";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { UserType, UserVerification } from "../types/vote";
-import { useProgram } from "./useProgram";
+import { UserVerification } from "../types/vote";
+import {
+  CATEGORY_REGISTRY_PDA,
+  ORGANIZATION_PDA,
+  useProgram,
+} from "./useProgram";
 import { useWallet } from "@solana/wallet-adapter-react";
 
 export const useUserVerification = () => {
@@ -16,7 +20,11 @@ export const useUserVerification = () => {
       if (!program) return null;
 
       const [pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_verification"), userPubkey.toBuffer()],
+        [
+          Buffer.from("user_verification"),
+          ORGANIZATION_PDA.toBuffer(),
+          userPubkey.toBuffer(),
+        ],
         program.programId
       );
       return pda;
@@ -24,8 +32,9 @@ export const useUserVerification = () => {
     [program]
   );
 
-  const verifyUser = useCallback(
-    async (idNumber: string, userType: UserType) => {
+  // Submits a pending request; a registrar of the organisation approves it
+  const requestVerification = useCallback(
+    async (idNumber: string, userType: number) => {
       if (!program || !publicKey) {
         throw new Error("Program or wallet not connected");
       }
@@ -38,10 +47,13 @@ export const useUserVerification = () => {
         if (!userVerificationPDA) throw new Error("Could not derive PDA");
 
         const tx = await program.methods
-          .verifyUser(idNumber, userType)
+          .requestVerification(idNumber, userType)
           .accounts({
             user: publicKey,
+            payer: publicKey,
+            organization: ORGANIZATION_PDA,
             userVerification: userVerificationPDA,
+            categoryRegistry: CATEGORY_REGISTRY_PDA,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -77,7 +89,7 @@ export const useUserVerification = () => {
   );
 
   return {
-    verifyUser,
+    requestVerification,
     fetchVerification,
     isLoading,
     error,
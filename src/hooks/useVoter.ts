@@ -1,9 +1,9 @@
 // hooks/useVoter.ts
 import { useCallback, useState } from "react";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { useProgram } from "./useProgram";
+import { ORGANIZATION_PDA, useProgram } from "./useProgram";
 import { useWallet } from "@solana/wallet-adapter-react";
-import { VoterStatus, ElectionVoter } from "../types/vote";
+import { VoterStatus, StatusReason, ElectionVoter } from "../types/vote";
 
 export const useVoter = (electionPDA?: PublicKey) => {
   const { program } = useProgram();
@@ -32,16 +32,23 @@ export const useVoter = (electionPDA?: PublicKey) => {
     try {
       const electionVoterPDA = getElectionVoterPDA(electionPDA, publicKey);
       const userVerificationPDA = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_verification"), publicKey.toBuffer()],
+        [
+          Buffer.from("user_verification"),
+          ORGANIZATION_PDA.toBuffer(),
+          publicKey.toBuffer(),
+        ],
         program.programId
       )[0];
 
       if (!electionVoterPDA) throw new Error("Could not derive PDA");
 
+      // Verified voters need no allowlist proof
       const tx = await program.methods
-        .registerVoter()
+        .registerVoter([])
         .accounts({
           voter: publicKey,
+          payer: publicKey,
+          sponsorship: null,
           election: electionPDA,
           electionVoter: electionVoterPDA,
           userVerification: userVerificationPDA,
@@ -58,7 +65,11 @@ export const useVoter = (electionPDA?: PublicKey) => {
 
   // Update voter status
   const updateVoterStatus = useCallback(
-    async (voter: PublicKey, newStatus: VoterStatus) => {
+    async (
+      voter: PublicKey,
+      newStatus: VoterStatus,
+      reason: StatusReason = { administrative: {} }
+    ) => {
       if (!program || !publicKey || !electionPDA)
         throw new Error("Missing required parameters");
       setIsLoading(true);
@@ -68,11 +79,12 @@ export const useVoter = (electionPDA?: PublicKey) => {
         if (!electionVoterPDA) throw new Error("Could not derive PDA");
 
         const tx = await program.methods
-          .updateVoterStatus(newStatus)
+          .updateVoterStatus(newStatus, reason)
           .accounts({
             authority: publicKey,
             election: electionPDA,
             electionVoter: electionVoterPDA,
+            systemProgram: SystemProgram.programId,
           })
           .rpc();
 
@@ -1,5 +1,6 @@
 // types/vote.ts
 import { PublicKey } from "@solana/web3.js";
+import { BN } from "@coral-xyz/anchor";
 
 export type Vote = {
   version: "0.1.0";
@@ -21,6 +22,25 @@ export interface VoterStatus {
   onHold?: {};
 }
 
+export interface StatusReason {
+  verified?: {};
+  administrative?: {};
+  ineligible?: {};
+  misconduct?: {};
+  fraud?: {};
+  appeal?: {};
+}
+
+export interface VotingMethod {
+  d21?: {};
+  plurality?: {};
+  approval?: {};
+  borda?: {};
+  instantRunoff?: {};
+  singleTransferable?: {};
+  quadratic?: {};
+}
+
 export interface ElectionStatus {
   created?: {};
   active?: {};
@@ -45,6 +65,23 @@ export interface UserVerified {
 }
 
 // types/election.ts
+export interface ElectionConfig {
+  numWinners: number;
+  numPlusVotes: number;
+  numMinusVotes: number;
+  allowedVoterTypes: number[]; // Ids from the organisation's category registry
+  startTime: BN | null;
+  endTime: BN | null;
+  allowRevote: boolean;
+  secretBallot: boolean;
+  votingMethod: VotingMethod;
+  creditBudget: number;
+  voteMint: PublicKey | null;
+  nftCollection: PublicKey | null;
+  oneVotePerNft: boolean;
+  voterMerkleRoot: number[] | null;
+}
+
 export interface Election {
   authority: PublicKey;
   bump: number;